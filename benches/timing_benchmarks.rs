@@ -106,11 +106,13 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use qwiic_relay_rs::QwiicRelayConfig;
+    // Imports live inside the tests: with `harness = false` the test functions are
+    // compiled out, and module-level imports would be flagged as unused.
 
     #[test]
     fn test_timing_config_variations() {
+        use qwiic_relay_rs::QwiicRelayConfig;
+
         // Test that different configs produce different timing values
         let standard = QwiicRelayConfig::new(4);
         let solid_state = QwiicRelayConfig::for_solid_state(4);
@@ -126,6 +128,8 @@ mod tests {
 
     #[test]
     fn test_timing_ranges() {
+        use qwiic_relay_rs::QwiicRelayConfig;
+
         // Test that timing values are within reasonable ranges
         let configs = vec![
             QwiicRelayConfig::new(4),
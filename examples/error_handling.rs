@@ -1,4 +1,4 @@
-use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig, RelayError, VerificationConfig};
+use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig, RelayError, RelayStatus, VerificationConfig};
 
 fn main() {
     println!("Qwiic Relay Error Handling Demo");
@@ -9,7 +9,7 @@ fn main() {
         .with_max_retries(1)
         .with_timeout(500);
     
-    let config = QwiicRelayConfig::with_verification(4, verification);
+    let config = QwiicRelayConfig::new(4).with_verification(verification);
     
     // Try to connect to relay board
     match QwiicRelay::new(config, "/dev/i2c-1", 0x08) {
@@ -47,12 +47,22 @@ fn main() {
                     println!("     - Operation: {}", operation);
                     println!("     - Timeout: {}ms", duration_ms);
                 }
+                Err(RelayError::VerificationFailed {
+                    relay_num,
+                    expected,
+                    attempts,
+                }) => {
+                    println!("   ✗ Verification failed!");
+                    println!("     - Relay: {:?}", relay_num);
+                    println!("     - Expected: {:?}", expected);
+                    println!("     - Attempts made: {}", attempts);
+                }
                 Err(RelayError::I2C(e)) => {
                     println!("   ✗ I2C communication error: {}", e);
                     println!("     Check your wiring and I2C connection");
                 }
-                Err(RelayError::InvalidConfiguration(msg)) => {
-                    println!("   ✗ Configuration error: {}", msg);
+                Err(e) => {
+                    println!("   ✗ Unexpected error: {}", e);
                 }
             }
             
@@ -63,7 +73,7 @@ fn main() {
             match relay.get_relay_state(Some(1)) {
                 Ok(state) => {
                     println!("   ✓ Relay 1 is currently: {}", 
-                        if state { "ON" } else { "OFF" });
+                        if state == RelayStatus::On { "ON" } else { "OFF" });
                 }
                 Err(e) => {
                     println!("   ✗ Failed to read relay state: {}", e);
@@ -110,8 +120,8 @@ fn main() {
                 Ok(_) => {
                     println!("   ✓ Address changed (unexpected!)");
                 }
-                Err(RelayError::InvalidConfiguration(msg)) => {
-                    println!("   ✓ Correctly caught invalid configuration: {}", msg);
+                Err(RelayError::InvalidI2CAddress(addr)) => {
+                    println!("   ✓ Correctly rejected invalid address 0x{:02X}", addr);
                 }
                 Err(e) => {
                     println!("   ✗ Unexpected error: {}", e);
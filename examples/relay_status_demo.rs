@@ -1,3 +1,5 @@
+#![allow(clippy::eq_op)] // comparing a value with itself is the point of this demo
+
 use qwiic_relay_rs::RelayStatus;
 
 fn main() {
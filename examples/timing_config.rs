@@ -1,13 +1,11 @@
-/// Example demonstrating configurable I2C timing features
-/// 
-/// This example shows how to:
-/// - Use different timing configurations for different relay types
-/// - Adjust timing at runtime
-/// - Auto-detect optimal timing settings
+//! Example demonstrating configurable I2C timing features
+//!
+//! This example shows how to:
+//! - Use different timing configurations for different relay types
+//! - Adjust timing at runtime
+//! - Auto-detect optimal timing settings
 
-use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
-use std::thread;
-use std::time::Duration;
+use qwiic_relay_rs::QwiicRelayConfig;
 
 fn main() {
     println!("Qwiic Relay Timing Configuration Example");
@@ -50,6 +48,10 @@ fn main() {
     
     // Uncomment the following code if you have hardware connected
     /*
+    use qwiic_relay_rs::QwiicRelay;
+    use std::thread;
+    use std::time::Duration;
+
     let config = QwiicRelayConfig::default();
     let mut relay = match QwiicRelay::new(config, "/dev/i2c-1", 0x08) {
         Ok(r) => r,
@@ -59,19 +61,13 @@ fn main() {
         }
     };
 
-    // Initialize the relay
-    if let Err(e) = relay.init() {
-        println!("   Failed to initialize: {:?}", e);
-        return;
-    }
-
     println!("   Initial write delay: {}μs", relay.config.write_delay_us);
     
     // Adjust timing at runtime
-    relay.set_write_delay(15);
+    relay.set_write_delay(15).expect("valid write delay");
     println!("   Adjusted write delay: {}μs", relay.config.write_delay_us);
     
-    relay.set_state_change_delay(20);
+    relay.set_state_change_delay(20).expect("valid state change delay");
     println!("   Adjusted state change delay: {}ms", relay.config.state_change_delay_ms);
 
     // Test relay operations with new timing
@@ -100,6 +96,8 @@ fn main() {
     
     // Uncomment the following code if you have hardware connected
     /*
+    use qwiic_relay_rs::QwiicRelay;
+
     let config = QwiicRelayConfig::default();
     let mut relay = match QwiicRelay::new(config, "/dev/i2c-1", 0x08) {
         Ok(r) => r,
@@ -109,11 +107,6 @@ fn main() {
         }
     };
 
-    if let Err(e) = relay.init() {
-        println!("   Failed to initialize: {:?}", e);
-        return;
-    }
-
     println!("   Starting auto-detection...");
     println!("   Initial timing: write={}μs, state_change={}ms", 
         relay.config.write_delay_us, 
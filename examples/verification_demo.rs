@@ -12,10 +12,7 @@ fn main() {
     println!("   - Retries up to 3 times on failure");
     println!("   - 1 second timeout\n");
     
-    let strict_config = QwiicRelayConfig::with_verification(
-        4,
-        VerificationConfig::strict()
-    );
+    let strict_config = QwiicRelayConfig::new(4).with_verification(VerificationConfig::strict());
     
     match QwiicRelay::new(strict_config, "/dev/i2c-1", 0x08) {
         Ok(mut relay) => {
@@ -44,10 +41,7 @@ fn main() {
     println!("   - 2 second timeout");
     println!("   - Better for noisy environments\n");
     
-    let lenient_config = QwiicRelayConfig::with_verification(
-        4,
-        VerificationConfig::lenient()
-    );
+    let lenient_config = QwiicRelayConfig::new(4).with_verification(VerificationConfig::lenient());
     
     match QwiicRelay::new(lenient_config, "/dev/i2c-1", 0x08) {
         Ok(mut relay) => {
@@ -84,7 +78,7 @@ fn main() {
         .with_verification_delay(50)
         .with_timeout(500);
     
-    let custom_config = QwiicRelayConfig::with_verification(4, custom_verification);
+    let custom_config = QwiicRelayConfig::new(4).with_verification(custom_verification);
     
     match QwiicRelay::new(custom_config, "/dev/i2c-1", 0x08) {
         Ok(mut relay) => {
@@ -110,10 +104,7 @@ fn main() {
     println!("   - Fastest operation");
     println!("   - Use when verification is not needed\n");
     
-    let disabled_config = QwiicRelayConfig::with_verification(
-        4,
-        VerificationConfig::disabled()
-    );
+    let disabled_config = QwiicRelayConfig::new(4).with_verification(VerificationConfig::disabled());
     
     match QwiicRelay::new(disabled_config, "/dev/i2c-1", 0x08) {
         Ok(mut relay) => {
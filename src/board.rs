@@ -0,0 +1,178 @@
+use crate::{Addresses, QwiicRelayConfig, VerificationConfig};
+
+/// The SparkFun Qwiic Relay boards this crate supports.
+///
+/// A board kind knows its I2C addresses, relay count, command set and timing,
+/// so [`BoardKind::config`] yields a configuration that matches the hardware.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BoardKind {
+    /// SparkFun Qwiic Single Relay.
+    SingleRelay,
+    /// SparkFun Qwiic Quad Relay (mechanical).
+    QuadRelay,
+    /// SparkFun Qwiic Dual Solid State Relay.
+    DualSolidState,
+    /// SparkFun Qwiic Quad Solid State Relay.
+    QuadSolidState,
+}
+
+/// Milliseconds between probes while waiting for a board to answer.
+pub(crate) const INIT_POLL_INTERVAL_MS: u32 = 10;
+
+/// What the drivers' `init()` found at their address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BoardInfo {
+    /// The 7-bit address that answered.
+    pub address: u8,
+    /// The board type the driver was created for, or else the one whose
+    /// address and relay count match.
+    pub kind: Option<BoardKind>,
+    /// The firmware version. Only single relay boards expose one; on multi
+    /// relay boards the version register is a toggle command, so it is only
+    /// read by a driver created for a single relay board.
+    pub firmware_version: Option<u8>,
+}
+
+/// Which command set a board understands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CommandSet {
+    /// Direct on/off commands (0x00/0x01), status at 0x05, version at 0x04.
+    Single,
+    /// Per-relay toggle commands (0x01-0x04), status at 0x05-0x08, all on/off/toggle.
+    Multi,
+}
+
+/// How quickly a board's switching elements settle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TimingProfile {
+    /// Electronic switching with no contact bounce.
+    SolidState,
+    /// Physical contacts that need time to move and settle.
+    Mechanical,
+}
+
+impl TimingProfile {
+    /// Microseconds to wait after each I2C write.
+    pub fn write_delay_us(self) -> u32 {
+        match self {
+            TimingProfile::SolidState => 5,
+            TimingProfile::Mechanical => 15,
+        }
+    }
+
+    /// Milliseconds to wait for a relay to change state.
+    pub fn state_change_delay_ms(self) -> u32 {
+        match self {
+            TimingProfile::SolidState => 5,
+            TimingProfile::Mechanical => 20,
+        }
+    }
+
+    /// Milliseconds the board needs after power-up.
+    pub fn init_delay_ms(self) -> u32 {
+        match self {
+            TimingProfile::SolidState => 100,
+            TimingProfile::Mechanical => 250,
+        }
+    }
+
+    /// Verification settings matched to the switching physics.
+    pub fn verification(self) -> VerificationConfig {
+        match self {
+            TimingProfile::SolidState => VerificationConfig::strict()
+                .with_verification_delay(1)
+                .with_retry_delay(10)
+                .with_timeout(250),
+            TimingProfile::Mechanical => VerificationConfig::strict()
+                .with_max_retries(5)
+                .with_verification_delay(25)
+                .with_retry_delay(50)
+                .with_timeout(1500),
+        }
+    }
+}
+
+impl BoardKind {
+    /// All supported boards.
+    pub const ALL: [BoardKind; 4] = [
+        BoardKind::SingleRelay,
+        BoardKind::QuadRelay,
+        BoardKind::DualSolidState,
+        BoardKind::QuadSolidState,
+    ];
+
+    /// Factory default I2C address.
+    pub fn default_address(self) -> u8 {
+        match self {
+            BoardKind::SingleRelay => Addresses::SingleRelayDefault as u8,
+            BoardKind::QuadRelay => Addresses::QuadRelayDefault as u8,
+            BoardKind::DualSolidState => Addresses::DualSolidState as u8,
+            BoardKind::QuadSolidState => Addresses::QuadSolidState as u8,
+        }
+    }
+
+    /// I2C address with the address jumper closed.
+    pub fn jumper_address(self) -> u8 {
+        match self {
+            BoardKind::SingleRelay => Addresses::SingleRelayJumperClosed as u8,
+            BoardKind::QuadRelay => Addresses::QuadRelayJumperClosed as u8,
+            BoardKind::DualSolidState => Addresses::DualSolidStateJumperClosed as u8,
+            BoardKind::QuadSolidState => Addresses::QuadSolidStateJumperClosed as u8,
+        }
+    }
+
+    /// I2C address for the given jumper setting.
+    pub fn address(self, jumper_closed: bool) -> u8 {
+        if jumper_closed {
+            self.jumper_address()
+        } else {
+            self.default_address()
+        }
+    }
+
+    /// Returns the board whose default or jumper address is `address`, if any.
+    pub fn from_address(address: u8) -> Option<BoardKind> {
+        BoardKind::ALL
+            .into_iter()
+            .find(|kind| kind.default_address() == address || kind.jumper_address() == address)
+    }
+
+    /// Number of relays on the board.
+    pub fn relay_count(self) -> u8 {
+        match self {
+            BoardKind::SingleRelay => 1,
+            BoardKind::DualSolidState => 2,
+            BoardKind::QuadRelay | BoardKind::QuadSolidState => 4,
+        }
+    }
+
+    /// Command set understood by the board.
+    pub fn command_set(self) -> CommandSet {
+        match self {
+            BoardKind::SingleRelay => CommandSet::Single,
+            _ => CommandSet::Multi,
+        }
+    }
+
+    /// Timing class of the board's relays.
+    pub fn timing_profile(self) -> TimingProfile {
+        match self {
+            BoardKind::SingleRelay | BoardKind::QuadRelay => TimingProfile::Mechanical,
+            BoardKind::DualSolidState | BoardKind::QuadSolidState => TimingProfile::SolidState,
+        }
+    }
+
+    /// Whether the board's firmware supports per-relay slow PWM.
+    pub fn supports_pwm(self) -> bool {
+        self.timing_profile() == TimingProfile::SolidState
+    }
+
+    /// Configuration with this board's relay count, timing and verification.
+    pub fn config(self) -> QwiicRelayConfig {
+        QwiicRelayConfig::for_profile(self.relay_count(), self.timing_profile())
+    }
+}
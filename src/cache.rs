@@ -0,0 +1,125 @@
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::registers::{self, Channel};
+use crate::{QwiicRelay, RelayResult, RelayStatus};
+
+/// How far [`QwiicRelay`] trusts its copy of the relay states.
+///
+/// Switching a relay on a quad board normally reads its status register first
+/// to pick the toggle command. With a cache, that read comes from the state the
+/// driver last wrote or read instead. Verification always reads the board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Read the board every time a relay state is needed (the default).
+    #[default]
+    AlwaysRead,
+    /// Use the cached states until an error, an address change or
+    /// [`QwiicRelay::sync`] replaces them.
+    ///
+    /// A relay switched by anything other than this driver goes unnoticed, and a
+    /// write may be skipped because the cache says the relay is already there.
+    Trust,
+    /// Use the cached states, but drop them after `n` cached lookups so the
+    /// board is read again. `CheckEvery(0)` reads every time.
+    CheckEvery(u32),
+}
+
+/// The driver's copy of the relay states; bit `n - 1` is relay `n`.
+///
+/// The bitmaps are 16 bits wide to cover every relay count the configuration
+/// allows, although relay masks only reach relay 8.
+#[derive(Debug, Default)]
+pub(crate) struct StateCache {
+    policy: CachePolicy,
+    /// Relays whose state is cached.
+    known: u16,
+    states: u16,
+    /// Lookups answered from the cache since it was last dropped.
+    hits: u32,
+}
+
+impl StateCache {
+    /// The cached state of `channel`, if the policy allows using it.
+    pub(crate) fn lookup(&mut self, channel: Channel) -> Option<RelayStatus> {
+        match self.policy {
+            CachePolicy::AlwaysRead => return None,
+            CachePolicy::Trust => {}
+            CachePolicy::CheckEvery(n) if self.hits >= n => {
+                self.invalidate();
+                return None;
+            }
+            CachePolicy::CheckEvery(_) => {}
+        }
+
+        let bit = 1u16 << channel.bit();
+        if self.known & bit == 0 {
+            return None;
+        }
+        self.hits = self.hits.saturating_add(1);
+        Some(RelayStatus::from(self.states & bit != 0))
+    }
+
+    /// Records a state that was read from or written to the board.
+    pub(crate) fn record(&mut self, channel: Channel, status: RelayStatus) {
+        let bit = 1u16 << channel.bit();
+        self.known |= bit;
+        match status {
+            RelayStatus::On => self.states |= bit,
+            RelayStatus::Off => self.states &= !bit,
+        }
+    }
+
+    /// Forgets every cached state.
+    pub(crate) fn invalidate(&mut self) {
+        self.known = 0;
+        self.hits = 0;
+    }
+}
+
+impl<I2C: BlockingI2c> QwiicRelay<I2C> {
+    /// Chooses when relay states may come from the driver's cache.
+    ///
+    /// The cache starts empty, so the first lookup of each relay still reads
+    /// the board.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, CachePolicy, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// relay.set_cache_policy(CachePolicy::CheckEvery(20));
+    /// relay.sync().unwrap();
+    /// relay.set_relay_on(Some(1)).unwrap(); // no status read before the toggle
+    /// ```
+    pub fn set_cache_policy(&mut self, policy: CachePolicy) {
+        self.cache = StateCache {
+            policy,
+            ..StateCache::default()
+        };
+    }
+
+    /// Returns the current cache policy.
+    pub fn cache_policy(&self) -> CachePolicy {
+        self.cache.policy
+    }
+
+    /// Reads every relay from the board and replaces the cached states.
+    ///
+    /// # Returns
+    /// The states as a bitmask, like [`QwiicRelay::get_relays`].
+    pub fn sync(&mut self) -> RelayResult<u8, I2C::Error> {
+        self.cache.invalidate();
+        let mut mask = 0;
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            if self.read_relay_state(channel)? == RelayStatus::On {
+                mask |= 1 << bit;
+            }
+        }
+        Ok(mask)
+    }
+
+    /// Forgets the cached states so the next lookup of each relay reads the board.
+    pub fn invalidate_cache(&mut self) {
+        self.cache.invalidate();
+    }
+}
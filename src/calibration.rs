@@ -0,0 +1,243 @@
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::registers::Channel;
+use crate::{QwiicRelay, QwiicRelayConfig, RelayResult, RelayStatus};
+
+/// Number of toggle-and-read trials a candidate timing must pass.
+const CALIBRATION_TRIALS: u8 = 3;
+
+/// One timing candidate tried during calibration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalibrationStep {
+    pub write_delay_us: u32,
+    pub state_change_delay_ms: u32,
+    /// Whether every trial read the relay back in the expected state.
+    pub passed: bool,
+}
+
+/// What [`QwiicRelay::calibrate_timing`] measured.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationReport {
+    /// The relay that was toggled.
+    pub relay_num: Option<u8>,
+    /// Trials run for each candidate.
+    pub trials: u8,
+    /// Smallest write delay that passed every trial.
+    pub min_write_delay_us: u32,
+    /// Smallest state change delay that passed every trial.
+    pub min_state_change_delay_ms: u32,
+    /// Every candidate tried, in order.
+    pub steps: Vec<CalibrationStep>,
+}
+
+impl fmt::Display for CalibrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "minimum write delay {}us, minimum state change {}ms ({} candidates, {} trials each)",
+            self.min_write_delay_us,
+            self.min_state_change_delay_ms,
+            self.steps.len(),
+            self.trials
+        )
+    }
+}
+
+/// Adds a 50% safety margin, never going below 1.
+fn with_margin(minimum: u32) -> u32 {
+    (minimum + minimum.div_ceil(2)).max(1)
+}
+
+impl<I2C: BlockingI2c> QwiicRelay<I2C> {
+    /// Measures the fastest timing this board switches reliably with.
+    ///
+    /// The relay is toggled and read back once per trial, without verification
+    /// retries, while `write_delay_us` and then `state_change_delay_ms` are
+    /// binary-searched between zero and their current values. The smallest
+    /// passing values get a 50% safety margin in the returned configuration.
+    /// The current configuration is not changed and the relay is left in the
+    /// state it started in.
+    ///
+    /// Returns `None` if the current timing itself fails, since there is then
+    /// nothing safe to shrink from. The relay is still restored, after waiting
+    /// out the verification timeout.
+    ///
+    /// # Arguments
+    /// * `relay_num` - The relay to toggle (None for single relay boards)
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
+    ///
+    /// let mut relay = QwiicRelay::new(QwiicRelayConfig::default(), "/dev/i2c-1", 0x08).unwrap();
+    /// if let Some((config, report)) = relay.calibrate_timing(Some(1)).unwrap() {
+    ///     println!("{}", report);
+    ///     relay.update_config(config).unwrap();
+    /// }
+    /// ```
+    pub fn calibrate_timing(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<Option<(QwiicRelayConfig, CalibrationReport)>, I2C::Error> {
+        let original = self.config;
+        let initial_state = self.read_relay_state(self.resolve_channel(relay_num)?)?;
+
+        let result = self.run_calibration(relay_num, original);
+
+        self.config = original;
+        self.settle(original);
+        if matches!(result, Ok(None)) {
+            // The board is slower than the current timing, so give it the whole
+            // verification window before trusting a read
+            thread::sleep(Duration::from_millis(original.verification.timeout_ms));
+        }
+        if self.read_relay_state(self.resolve_channel(relay_num)?)? != initial_state {
+            self.set_relay(relay_num, initial_state)?;
+        }
+
+        let Some(report) = result? else {
+            return Ok(None);
+        };
+        let config = original
+            .with_write_delay_us(with_margin(report.min_write_delay_us))
+            .with_state_change_delay_ms(with_margin(report.min_state_change_delay_ms));
+        Ok(Some((config, report)))
+    }
+
+    /// Calibrates the timing and applies it if it is faster than the current one.
+    ///
+    /// Uses relay 1 (or the single relay) and [`QwiicRelay::calibrate_timing`].
+    ///
+    /// # Returns
+    /// `true` if a faster timing was found and applied, `false` if the current
+    /// timing is kept.
+    pub fn auto_detect_timing(&mut self) -> RelayResult<bool, I2C::Error> {
+        let relay_num = Channel::primary(self.board, self.config.relay_count).relay_num();
+
+        let Some((config, _)) = self.calibrate_timing(relay_num)? else {
+            return Ok(false);
+        };
+        let faster = config.write_delay_us < self.config.write_delay_us
+            || config.state_change_delay_ms < self.config.state_change_delay_ms;
+        if !faster {
+            return Ok(false);
+        }
+
+        // Never slow down either delay while speeding up the other
+        let config = config
+            .with_write_delay_us(config.write_delay_us.min(self.config.write_delay_us))
+            .with_state_change_delay_ms(
+                config.state_change_delay_ms.min(self.config.state_change_delay_ms),
+            );
+        self.update_config(config)?;
+        Ok(true)
+    }
+
+    fn run_calibration(
+        &mut self,
+        relay_num: Option<u8>,
+        original: QwiicRelayConfig,
+    ) -> RelayResult<Option<CalibrationReport>, I2C::Error> {
+        let mut steps = Vec::new();
+
+        if !self.try_timing(relay_num, original, &mut steps)? {
+            return Ok(None);
+        }
+
+        let min_write_delay_us = self.search(
+            0,
+            original.write_delay_us,
+            |write_delay_us| original.with_write_delay_us(write_delay_us),
+            relay_num,
+            original,
+            &mut steps,
+        )?;
+
+        let write_calibrated = original.with_write_delay_us(with_margin(min_write_delay_us));
+        let min_state_change_delay_ms = self.search(
+            0,
+            original.state_change_delay_ms,
+            |state_change_delay_ms| write_calibrated.with_state_change_delay_ms(state_change_delay_ms),
+            relay_num,
+            original,
+            &mut steps,
+        )?;
+
+        Ok(Some(CalibrationReport {
+            relay_num,
+            trials: CALIBRATION_TRIALS,
+            min_write_delay_us,
+            min_state_change_delay_ms,
+            steps,
+        }))
+    }
+
+    /// Binary-searches the smallest value in `low..=high` whose config passes; `high` is known to pass.
+    fn search(
+        &mut self,
+        mut low: u32,
+        mut high: u32,
+        candidate: impl Fn(u32) -> QwiicRelayConfig,
+        relay_num: Option<u8>,
+        original: QwiicRelayConfig,
+        steps: &mut Vec<CalibrationStep>,
+    ) -> RelayResult<u32, I2C::Error> {
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.try_timing(relay_num, candidate(mid), steps)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+                // A failed trial may leave the relay mid-switch
+                self.settle(original);
+            }
+        }
+        Ok(high)
+    }
+
+    /// Runs the trials for one candidate timing and records the step.
+    fn try_timing(
+        &mut self,
+        relay_num: Option<u8>,
+        timing: QwiicRelayConfig,
+        steps: &mut Vec<CalibrationStep>,
+    ) -> RelayResult<bool, I2C::Error> {
+        self.config = timing;
+        let mut passed = true;
+        for _ in 0..CALIBRATION_TRIALS {
+            let current_state = self.read_relay_state(self.resolve_channel(relay_num)?)?;
+            let expected_state = if current_state == RelayStatus::On {
+                RelayStatus::Off
+            } else {
+                RelayStatus::On
+            };
+
+            let command = self.resolve_channel(relay_num)?.toggle_command(current_state);
+            self.write_command(command)?;
+            self.delay_after_write();
+            self.wait_for_state_change();
+
+            if self.read_relay_state(self.resolve_channel(relay_num)?)? != expected_state {
+                passed = false;
+                break;
+            }
+        }
+
+        steps.push(CalibrationStep {
+            write_delay_us: timing.write_delay_us,
+            state_change_delay_ms: timing.state_change_delay_ms,
+            passed,
+        });
+        Ok(passed)
+    }
+
+    /// Waits long enough under the known-good timing for any switch to finish.
+    fn settle(&self, original: QwiicRelayConfig) {
+        let settle_ms = original.state_change_delay_ms as u64 + original.verification.verification_delay_ms;
+        thread::sleep(Duration::from_millis(settle_ms));
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use embedded_hal::digital::{ErrorType, OutputPin, StatefulOutputPin};
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::registers::Channel;
+use crate::typestate::{self, Off, On};
+use crate::{QwiicRelay, RelayError, RelayResult, RelayStatus, VerificationOutcome};
+
+/// One relay on a board, borrowed from its [`QwiicRelay`].
+///
+/// The relay number is checked once, when the channel is created, so the
+/// methods here cannot address the wrong relay. Relays are numbered from 1 on
+/// every board, including the single relay board's only relay.
+///
+/// The channel also implements embedded-hal's [`OutputPin`] (high is on) and
+/// [`StatefulOutputPin`], so a relay can be handed to code written for pins.
+/// Through those traits a Lenient-mode verification warning is not an error;
+/// it is still recorded in [`QwiicRelay::diagnostics`].
+#[derive(Debug)]
+pub struct RelayChannel<'a, I2C: BlockingI2c> {
+    relay: &'a mut QwiicRelay<I2C>,
+    channel: Channel,
+}
+
+impl<I2C: BlockingI2c> QwiicRelay<I2C> {
+    /// Borrows one relay as a [`RelayChannel`].
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number, from 1 (1 is the only relay on single relay boards)
+    ///
+    /// # Errors
+    /// `InvalidRelayNumber` if the board has no such relay.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// let mut pump = relay.channel(3).unwrap();
+    /// pump.on().unwrap();
+    /// pump.pulse(Duration::from_secs(5)).unwrap();
+    /// ```
+    pub fn channel(&mut self, relay_num: u8) -> RelayResult<RelayChannel<'_, I2C>, I2C::Error> {
+        let channel = self.numbered_channel(relay_num)?;
+        Ok(RelayChannel::new(self, channel))
+    }
+
+    /// Validates a relay number counted from 1 on every board.
+    pub(crate) fn numbered_channel(&self, relay_num: u8) -> RelayResult<Channel, I2C::Error> {
+        match Channel::primary(self.board, self.config.relay_count) {
+            Channel::Single if relay_num == 1 => Ok(Channel::Single),
+            _ => self.resolve_channel(Some(relay_num)),
+        }
+    }
+}
+
+impl<'a, I2C: BlockingI2c> RelayChannel<'a, I2C> {
+    /// Wraps a channel that has already been validated against the board.
+    pub(crate) fn new(relay: &'a mut QwiicRelay<I2C>, channel: Channel) -> RelayChannel<'a, I2C> {
+        RelayChannel { relay, channel }
+    }
+
+    /// The relay's number on the board.
+    pub fn relay_num(&self) -> u8 {
+        self.channel.relay_num().unwrap_or(1)
+    }
+
+    /// Turns the relay on. See [`QwiicRelay::set_relay_on`].
+    pub fn on(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relay(self.channel.relay_num(), RelayStatus::On)
+    }
+
+    /// Turns the relay off. See [`QwiicRelay::set_relay_off`].
+    pub fn off(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relay(self.channel.relay_num(), RelayStatus::Off)
+    }
+
+    /// Flips the relay. See [`QwiicRelay::toggle_relay`].
+    pub fn toggle(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.toggle_relay(self.channel.relay_num())
+    }
+
+    /// Reads the relay's state. See [`QwiicRelay::get_relay_state`].
+    pub fn state(&mut self) -> RelayResult<RelayStatus, I2C::Error> {
+        self.relay.get_relay_state(self.channel.relay_num())
+    }
+
+    /// Turns the relay on for `duration`, then restores it. See [`QwiicRelay::pulse`].
+    pub fn pulse(&mut self, duration: Duration) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.pulse(self.channel.relay_num(), duration)
+    }
+
+    /// Switches the relay off and tracks its state in the type from then on.
+    ///
+    /// See the [`typestate`](crate::typestate) module.
+    pub fn into_off(self) -> RelayResult<typestate::Relay<'a, Off, I2C>, I2C::Error> {
+        typestate::Relay::switched(self.relay, self.channel)
+    }
+
+    /// Switches the relay on and tracks its state in the type from then on.
+    pub fn into_on(self) -> RelayResult<typestate::Relay<'a, On, I2C>, I2C::Error> {
+        typestate::Relay::switched(self.relay, self.channel)
+    }
+}
+
+impl<I2C: BlockingI2c> ErrorType for RelayChannel<'_, I2C> {
+    type Error = RelayError<I2C::Error>;
+}
+
+impl<I2C: BlockingI2c> OutputPin for RelayChannel<'_, I2C> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.off().map(|_| ())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.on().map(|_| ())
+    }
+}
+
+impl<I2C: BlockingI2c> StatefulOutputPin for RelayChannel<'_, I2C> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.state()? == RelayStatus::On)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.state()? == RelayStatus::Off)
+    }
+
+    fn toggle(&mut self) -> Result<(), Self::Error> {
+        RelayChannel::toggle(self).map(|_| ())
+    }
+}
@@ -0,0 +1,182 @@
+use embedded_hal::i2c::{Error, ErrorKind, I2c as BlockingI2c};
+
+use crate::registers;
+use crate::{
+    Addresses, BoardKind, Command, LinuxBus, LinuxBusError, QwiicRelay, RelayResult, RelayState,
+};
+
+/// Every address a Qwiic Relay board answers at without being reprogrammed.
+pub const KNOWN_ADDRESSES: [u8; 8] = [
+    Addresses::SingleRelayDefault as u8,
+    Addresses::SingleRelayJumperClosed as u8,
+    Addresses::QuadRelayDefault as u8,
+    Addresses::QuadRelayJumperClosed as u8,
+    Addresses::DualSolidState as u8,
+    Addresses::DualSolidStateJumperClosed as u8,
+    Addresses::QuadSolidState as u8,
+    Addresses::QuadSolidStateJumperClosed as u8,
+];
+
+/// A relay board found on the bus.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DiscoveredBoard {
+    /// The 7-bit address the board answered at.
+    pub address: u8,
+    /// The board type, judged from its status registers and address.
+    pub kind: BoardKind,
+    /// The firmware version. Only single relay boards expose one.
+    pub firmware_version: Option<u8>,
+    /// True when the registers fit more than one board type and the address
+    /// did not settle it. Quad relay and quad solid state boards have the same
+    /// register map, so a quad board at a reprogrammed address is reported as a
+    /// `QuadRelay`, whose slower mechanical timing also suits solid state relays.
+    pub ambiguous: bool,
+}
+
+/// The outcome of a scan.
+#[derive(Debug)]
+pub struct Discovery<E = LinuxBusError> {
+    /// The boards found, in the order their addresses were scanned.
+    pub boards: Vec<DiscoveredBoard>,
+    /// Addresses that acknowledged but then failed a read, with the bus error.
+    /// The scan carries on past them.
+    pub errors: Vec<(u8, E)>,
+}
+
+impl DiscoveredBoard {
+    /// Creates a driver for this board on an existing embedded-hal I2C bus.
+    ///
+    /// The driver uses the board's address, relay count, command set and timing.
+    pub fn connect<I2C: BlockingI2c>(&self, i2c: I2C) -> QwiicRelay<I2C> {
+        let mut relay = QwiicRelay::from_i2c(self.kind.config(), i2c, self.address);
+        relay.board = Some(self.kind);
+        relay
+    }
+
+    /// Opens a Linux I2C bus and creates a driver for this board on it.
+    ///
+    /// # Arguments
+    /// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
+    pub fn open(&self, bus: &str) -> RelayResult<QwiicRelay> {
+        Ok(self.connect(LinuxBus::new(bus)?))
+    }
+}
+
+/// Scans the known Qwiic Relay addresses on a Linux I2C bus.
+///
+/// Only registers that are safe to read are touched, so no relay switches.
+///
+/// # Arguments
+/// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
+///
+/// # Examples
+/// ```no_run
+/// use qwiic_relay_rs::discover;
+///
+/// for board in discover("/dev/i2c-1").unwrap().boards {
+///     println!("{:?} at 0x{:02X}", board.kind, board.address);
+///     let mut relay = board.open("/dev/i2c-1").unwrap();
+///     relay.init().unwrap();
+/// }
+/// ```
+pub fn discover(bus: &str) -> RelayResult<Discovery> {
+    let mut bus = LinuxBus::new(bus)?;
+    Ok(discover_on(&mut bus, KNOWN_ADDRESSES))
+}
+
+/// Scans every address from 0x08 to 0x77 on a Linux I2C bus.
+///
+/// This finds boards that were moved with `change_i2c_address`. Every device
+/// that acknowledges is sent a register read, so only use it on buses whose
+/// other devices tolerate that.
+///
+/// # Arguments
+/// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
+pub fn discover_all(bus: &str) -> RelayResult<Discovery> {
+    let mut bus = LinuxBus::new(bus)?;
+    Ok(discover_on(&mut bus, 0x08..=0x77))
+}
+
+/// Scans the given addresses on an existing embedded-hal I2C bus.
+///
+/// Only the relay status registers (0x05-0x08), which are read-only on every
+/// board, are read to classify a device. They are read in order while each
+/// holds a status value (0 or 1): one such register means a single relay board,
+/// two a dual solid state board and four a quad board, whose type then comes
+/// from the address. A single relay board then has its firmware version (0x04)
+/// read, which on the other boards would be a toggle command. Devices whose
+/// registers match none of the boards are skipped, as are devices that NACK;
+/// an address whose reads fail otherwise is recorded in [`Discovery::errors`]
+/// without stopping the scan.
+///
+/// # Arguments
+/// * `i2c` - Any bus implementing `embedded_hal::i2c::I2c`
+/// * `addresses` - 7-bit addresses to probe
+pub fn discover_on<I2C: BlockingI2c>(
+    i2c: &mut I2C,
+    addresses: impl IntoIterator<Item = u8>,
+) -> Discovery<I2C::Error> {
+    let mut discovery = Discovery {
+        boards: Vec::new(),
+        errors: Vec::new(),
+    };
+    for address in addresses {
+        match probe(i2c, address) {
+            Ok(Some(board)) => discovery.boards.push(board),
+            Ok(None) => {}
+            Err(err) => discovery.errors.push((address, err)),
+        }
+    }
+    discovery
+}
+
+/// Classifies the device at `address`, or returns `None` if it is not a relay board.
+fn probe<I2C: BlockingI2c>(i2c: &mut I2C, address: u8) -> Result<Option<DiscoveredBoard>, I2C::Error> {
+    let mut status_registers = 0;
+    for register in Command::RelayOneStatus as u8..=Command::RelayFourStatus as u8 {
+        match read_register(i2c, address, register) {
+            Ok(status) if is_status(status) => status_registers += 1,
+            Ok(_) => break,
+            Err(err) if status_registers == 0 && is_nack(&err) => return Ok(None),
+            Err(err) => return Err(err),
+        }
+    }
+
+    let (kind, ambiguous) = match status_registers {
+        1 => (BoardKind::SingleRelay, false),
+        2 => (BoardKind::DualSolidState, false),
+        4 => match BoardKind::from_address(address) {
+            Some(kind) if kind.relay_count() == 4 => (kind, false),
+            _ => (BoardKind::QuadRelay, true),
+        },
+        _ => return Ok(None),
+    };
+
+    let firmware_version = if kind == BoardKind::SingleRelay {
+        Some(read_register(i2c, address, registers::FIRMWARE_VERSION)?)
+    } else {
+        None
+    };
+
+    Ok(Some(DiscoveredBoard {
+        address,
+        kind,
+        firmware_version,
+        ambiguous,
+    }))
+}
+
+/// Whether `value` is one a relay status register reports.
+fn is_status(value: u8) -> bool {
+    value == RelayState::Off as u8 || value == RelayState::On as u8
+}
+
+fn is_nack<E: Error>(err: &E) -> bool {
+    matches!(err.kind(), ErrorKind::NoAcknowledge(_))
+}
+
+fn read_register<I2C: BlockingI2c>(i2c: &mut I2C, address: u8, register: u8) -> Result<u8, I2C::Error> {
+    let mut buffer = [0u8];
+    i2c.write_read(address, &[register], &mut buffer)?;
+    Ok(buffer[0])
+}
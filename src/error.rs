@@ -1,11 +1,21 @@
-use std::error::Error;
-use std::fmt;
+use core::fmt;
+#[cfg(feature = "std")]
 use i2cdev::linux::LinuxI2CError;
 use crate::RelayStatus;
+#[cfg(feature = "std")]
+use crate::linux::LinuxBusError;
 
+/// Errors returned by relay operations, generic over the I2C bus error `E`.
+///
+/// The type does not allocate, so the std and async drivers share it. With the
+/// `std` feature the bus error defaults to `LinuxBusError`.
 #[derive(Debug)]
-pub enum RelayError {
-    I2C(LinuxI2CError),
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RelayError<
+    #[cfg(feature = "std")] E = LinuxBusError,
+    #[cfg(not(feature = "std"))] E,
+> {
+    I2C(E),
     StateVerificationFailed {
         relay_num: Option<u8>,
         expected: bool,
@@ -24,18 +34,39 @@ pub enum RelayError {
     },
     Timeout {
         relay_num: Option<u8>,
-        operation: String,
+        operation: &'static str,
         duration_ms: u64,
     },
-    InvalidConfiguration(String),
+    InvalidConfiguration(&'static str),
     InvalidRelayNumber {
         relay_num: u8,
         max_relays: u8,
     },
     InvalidI2CAddress(u8),
+    DeviceNotFound {
+        address: u8,
+        waited_ms: u64,
+    },
+    AddressInUse(u8),
+    AddressChangeFailed {
+        old_address: u8,
+        new_address: u8,
+    },
+}
+
+/// Formats an optional relay number as "relay N" or just "relay".
+struct RelayDesc(Option<u8>);
+
+impl fmt::Display for RelayDesc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(n) => write!(f, "relay {}", n),
+            None => write!(f, "relay"),
+        }
+    }
 }
 
-impl fmt::Display for RelayError {
+impl<E: fmt::Display> fmt::Display for RelayError<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             RelayError::I2C(err) => write!(f, "I2C error: {}", err),
@@ -45,13 +76,10 @@ impl fmt::Display for RelayError {
                 actual,
                 attempts,
             } => {
-                let relay_desc = relay_num
-                    .map(|n| format!("relay {}", n))
-                    .unwrap_or_else(|| "relay".to_string());
                 write!(
                     f,
                     "State verification failed for {}: expected {}, got {} after {} attempts",
-                    relay_desc,
+                    RelayDesc(*relay_num),
                     if *expected { "ON" } else { "OFF" },
                     if *actual { "ON" } else { "OFF" },
                     attempts
@@ -62,13 +90,10 @@ impl fmt::Display for RelayError {
                 expected,
                 attempts,
             } => {
-                let relay_desc = relay_num
-                    .map(|n| format!("relay {}", n))
-                    .unwrap_or_else(|| "relay".to_string());
                 write!(
                     f,
                     "Verification failed for {}: expected {:?} after {} attempts",
-                    relay_desc, expected, attempts
+                    RelayDesc(*relay_num), expected, attempts
                 )
             }
             RelayError::VerificationTimeout {
@@ -76,13 +101,10 @@ impl fmt::Display for RelayError {
                 expected,
                 timeout_ms,
             } => {
-                let relay_desc = relay_num
-                    .map(|n| format!("relay {}", n))
-                    .unwrap_or_else(|| "relay".to_string());
                 write!(
                     f,
                     "Verification timeout for {}: expected {:?} after {}ms",
-                    relay_desc, expected, timeout_ms
+                    RelayDesc(*relay_num), expected, timeout_ms
                 )
             }
             RelayError::Timeout {
@@ -90,13 +112,10 @@ impl fmt::Display for RelayError {
                 operation,
                 duration_ms,
             } => {
-                let relay_desc = relay_num
-                    .map(|n| format!("relay {}", n))
-                    .unwrap_or_else(|| "relay".to_string());
                 write!(
                     f,
                     "Timeout during {} for {}: exceeded {}ms",
-                    operation, relay_desc, duration_ms
+                    operation, RelayDesc(*relay_num), duration_ms
                 )
             }
             RelayError::InvalidConfiguration(msg) => {
@@ -115,16 +134,55 @@ impl fmt::Display for RelayError {
             RelayError::InvalidI2CAddress(addr) => {
                 write!(f, "Invalid I2C address 0x{:02X}: valid range is 0x08-0x77", addr)
             }
+            RelayError::DeviceNotFound { address, waited_ms } => {
+                write!(
+                    f,
+                    "No device responded at I2C address 0x{:02X} within {}ms",
+                    address, waited_ms
+                )
+            }
+            RelayError::AddressInUse(addr) => {
+                write!(f, "I2C address 0x{:02X} is already in use on the bus", addr)
+            }
+            RelayError::AddressChangeFailed {
+                old_address,
+                new_address,
+            } => {
+                write!(
+                    f,
+                    "Board did not move from 0x{:02X} to 0x{:02X}; it still answers at 0x{:02X}",
+                    old_address, new_address, old_address
+                )
+            }
         }
     }
 }
 
-impl Error for RelayError {}
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for RelayError<E> {}
 
+/// Lets relays stand in for output pins; every failure is reported as `Other`.
+impl<E: fmt::Debug> embedded_hal::digital::Error for RelayError<E> {
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        embedded_hal::digital::ErrorKind::Other
+    }
+}
+
+#[cfg(feature = "std")]
 impl From<LinuxI2CError> for RelayError {
     fn from(err: LinuxI2CError) -> Self {
+        RelayError::I2C(LinuxBusError(err))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<LinuxBusError> for RelayError {
+    fn from(err: LinuxBusError) -> Self {
         RelayError::I2C(err)
     }
 }
 
-pub type RelayResult<T> = Result<T, RelayError>;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub type RelayResult<T, E = LinuxBusError> = Result<T, RelayError<E>>;
+#[cfg(not(feature = "std"))]
+pub type RelayResult<T, E> = Result<T, RelayError<E>>;
@@ -37,14 +37,19 @@
 //!
 //! ## Embedded Example (embedded feature)
 //! ```no_run
-//! use qwiic_relay_rs::{QwiicRelayAsync, Addresses};
-//! use embedded_hal_async::i2c::I2c;
+//! # #[cfg(feature = "embedded")]
+//! # mod example {
+//! use qwiic_relay_rs::{Addresses, RelayError, QwiicRelayAsync, QwiicRelayConfig};
+//! use embedded_hal_async::{delay::DelayNs, i2c::I2c};
 //!
-//! async fn example<I2C: I2c>(i2c: I2C) -> Result<(), I2C::Error> {
-//!     let mut relay = QwiicRelayAsync::new(i2c, Addresses::QuadSolidState as u8)?;
+//! async fn example<I2C: I2c, D: DelayNs>(i2c: I2C, delay: D) -> Result<(), RelayError<I2C::Error>> {
+//!     let config = QwiicRelayConfig::new(4);
+//!     let mut relay = QwiicRelayAsync::new(config, i2c, delay, Addresses::QuadSolidState as u8)?;
 //!     relay.set_relay_on(Some(1)).await?;
+//!     relay.toggle_relay(Some(2)).await?;
 //!     Ok(())
 //! }
+//! # }
 //! ```
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -56,13 +61,43 @@
 
 // Embedded implementation
 #[cfg(feature = "embedded")]
+use embedded_hal_async::delay::DelayNs;
+#[cfg(feature = "embedded")]
 use embedded_hal_async::i2c::{self, I2c, SevenBitAddress};
 
+// Shared between both implementations
+#[cfg(any(feature = "std", feature = "embedded"))]
+mod board;
+#[cfg(any(feature = "std", feature = "embedded"))]
+mod error;
+#[cfg(any(feature = "std", feature = "embedded"))]
+mod registers;
+#[cfg(any(feature = "std", feature = "embedded"))]
+mod verification;
+
 // Linux implementation
 #[cfg(feature = "std")]
-mod error;
+mod cache;
 #[cfg(feature = "std")]
-mod verification;
+mod calibration;
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "std")]
+mod discovery;
+#[cfg(feature = "std")]
+mod linux;
+#[cfg(feature = "std")]
+mod pulse;
+#[cfg(feature = "std")]
+pub mod sequence;
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+pub mod sim;
+#[cfg(feature = "std")]
+pub mod typed;
+#[cfg(feature = "std")]
+pub mod typestate;
 
 #[cfg(feature = "std")]
 use std::thread;
@@ -70,14 +105,36 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 #[cfg(feature = "std")]
-use i2cdev::core::*;
-#[cfg(feature = "std")]
-use i2cdev::linux::LinuxI2CDevice;
+use embedded_hal::i2c::{Error as _, ErrorKind, I2c as BlockingI2c};
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "embedded"))]
+pub use board::{BoardInfo, BoardKind, CommandSet, TimingProfile};
+#[cfg(any(feature = "std", feature = "embedded"))]
 pub use error::{RelayError, RelayResult};
+#[cfg(any(feature = "std", feature = "embedded"))]
+use registers::Channel;
+#[cfg(any(feature = "std", feature = "embedded"))]
+pub use registers::PWM_MAX;
+#[cfg(feature = "std")]
+pub use cache::CachePolicy;
+#[cfg(feature = "std")]
+use cache::StateCache;
+#[cfg(feature = "std")]
+pub use calibration::{CalibrationReport, CalibrationStep};
 #[cfg(feature = "std")]
-pub use verification::{VerificationConfig, VerificationMode};
+pub use channel::RelayChannel;
+#[cfg(feature = "std")]
+pub use discovery::{DiscoveredBoard, Discovery, KNOWN_ADDRESSES, discover, discover_all, discover_on};
+#[cfg(feature = "std")]
+pub use linux::{LinuxBus, LinuxBusError};
+#[cfg(feature = "std")]
+pub use pulse::{PulseHandle, PulseRegistry};
+#[cfg(feature = "std")]
+pub use shared::SharedRelay;
+#[cfg(any(feature = "std", feature = "embedded"))]
+pub use verification::{
+    BankOutcome, VerificationConfig, VerificationMode, VerificationOutcome, VerificationWarning,
+};
 
 /// I2C addresses for different Qwiic Relay board configurations.
 #[derive(Debug, Copy, Clone)]
@@ -109,6 +166,10 @@ pub enum Command {
     TurnAllOff = 0x0A,
     TurnAllOn = 0x0B,
     ToggleAll = 0x0C,
+    RelayOnePwm = 0x10,
+    RelayTwoPwm = 0x11,
+    RelayThreePwm = 0x12,
+    RelayFourPwm = 0x13,
 }
 
 /// Relay state and control values.
@@ -129,137 +190,644 @@ pub enum Status {
     Off = 0,
 }
 
+/// Async interface for controlling a Qwiic Relay board from embedded firmware.
+///
+/// This mirrors the blocking `QwiicRelay` driver: it takes the same
+/// [`QwiicRelayConfig`], validates relay numbers and verifies state changes.
+/// All waits go through the supplied `DelayNs` implementation.
 #[cfg(feature = "embedded")]
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct QwiicRelayAsync<T> {
+pub struct QwiicRelayAsync<T, D> {
     i2c: T,
+    delay: D,
     i2c_addr: SevenBitAddress,
+    board: Option<BoardKind>,
+    /// The configuration for this relay board.
+    pub config: QwiicRelayConfig,
 }
 
 #[cfg(feature = "embedded")]
-impl<T: I2c<Error = E>, E: i2c::Error> QwiicRelayAsync<T> {
+impl<T: I2c<Error = E>, D: DelayNs, E: i2c::Error> QwiicRelayAsync<T, D> {
+    /// Creates a new QwiicRelayAsync instance.
+    ///
+    /// No I2C traffic happens here; call [`QwiicRelayAsync::init`] to wait for
+    /// the board to finish booting.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration for the relay board
+    /// * `i2c` - Any bus implementing `embedded_hal_async::i2c::I2c`
+    /// * `delay` - Delay provider used for write, settle and retry delays
+    /// * `i2c_addr` - 7-bit I2C address of the relay board
     pub fn new(
+        config: QwiicRelayConfig,
         i2c: T,
+        delay: D,
         i2c_addr: SevenBitAddress,
-    ) -> Result<QwiicRelayAsync<T>, E> {
+    ) -> RelayResult<QwiicRelayAsync<T, D>, E> {
+        if i2c_addr > 0x7F {
+            return Err(RelayError::InvalidI2CAddress(i2c_addr));
+        }
         Ok(QwiicRelayAsync {
             i2c,
+            delay,
             i2c_addr,
+            board: None,
+            config,
         })
     }
 
-    pub async fn set_relay_on(&mut self, relay_num: Option<u8>) -> Result<(), E> {
-        if let Some(num) = relay_num {
-            if !self.get_relay_state(relay_num).await? {
-                self.i2c
-                    .write(self.i2c_addr, &[Command::DualQuadToggleBase as u8 + num])
-                    .await?;
-            }
-        } else {
-            self.i2c
-                .write(self.i2c_addr, &[RelayState::On as u8])
-                .await?;
+    /// Creates a QwiicRelayAsync configured for a specific board.
+    ///
+    /// The address, relay count, command set and timing all come from `kind`.
+    ///
+    /// # Arguments
+    /// * `kind` - The board type
+    /// * `i2c` - Any bus implementing `embedded_hal_async::i2c::I2c`
+    /// * `delay` - Delay provider used for write, settle and retry delays
+    /// * `jumper_closed` - Whether the board's address jumper is closed
+    pub fn for_board(
+        kind: BoardKind,
+        i2c: T,
+        delay: D,
+        jumper_closed: bool,
+    ) -> QwiicRelayAsync<T, D> {
+        QwiicRelayAsync {
+            i2c,
+            delay,
+            i2c_addr: kind.address(jumper_closed),
+            board: Some(kind),
+            config: kind.config(),
         }
-        Ok(())
     }
 
-    pub async fn set_relay_off(&mut self, relay_num: Option<u8>) -> Result<(), E> {
-        if let Some(num) = relay_num {
-            let read_command = 0x04 + num;
-            let mut status = [0u8];
-            self.i2c
-                .write_read(self.i2c_addr, &[read_command], &mut status)
-                .await?;
+    /// Returns the board type, if the driver was created with [`QwiicRelayAsync::for_board`].
+    pub fn board(&self) -> Option<BoardKind> {
+        self.board
+    }
 
-            if status[0] != (Status::Off as u8) {
-                self.i2c
-                    .write(self.i2c_addr, &[Command::DualQuadToggleBase as u8 + num])
-                    .await?;
+    /// Waits for the board to answer and identifies it.
+    ///
+    /// Polls a register that is safe to read every 10ms until the board
+    /// acknowledges, for at most `init_delay_ms`. See
+    /// `QwiicRelay::init` for details.
+    pub async fn init(&mut self) -> RelayResult<BoardInfo, E> {
+        let (kind, register) = registers::probe_target(self.board, self.i2c_addr, self.config.relay_count);
+        let mut waited_ms = 0;
+
+        let response = loop {
+            match self.read_register(register).await {
+                Ok(response) => break response,
+                Err(RelayError::I2C(err)) if matches!(err.kind(), i2c::ErrorKind::NoAcknowledge(_)) => {
+                    if waited_ms >= self.config.init_delay_ms {
+                        return Err(RelayError::DeviceNotFound {
+                            address: self.i2c_addr,
+                            waited_ms: waited_ms as u64,
+                        });
+                    }
+                    self.delay.delay_ms(board::INIT_POLL_INTERVAL_MS).await;
+                    waited_ms += board::INIT_POLL_INTERVAL_MS;
+                }
+                Err(err) => return Err(err),
             }
-        } else {
-            self.i2c
-                .write(self.i2c_addr, &[RelayState::Off as u8])
-                .await?;
-        }
+        };
+        self.delay_after_write().await;
+
+        Ok(BoardInfo {
+            address: self.i2c_addr,
+            kind,
+            firmware_version: (register == registers::FIRMWARE_VERSION).then_some(response),
+        })
+    }
+
+    /// Replaces the configuration after validating it.
+    ///
+    /// See `QwiicRelay::update_config` for the checks performed.
+    pub fn update_config(&mut self, config: QwiicRelayConfig) -> RelayResult<(), E> {
+        config
+            .validate(self.board)
+            .map_err(RelayError::InvalidConfiguration)?;
+        self.config = config;
         Ok(())
     }
 
-    pub async fn get_relay_state(&mut self, relay_num: Option<u8>) -> Result<bool, E> {
-        let read_command = 0x04 + relay_num.unwrap_or(0);
-        let mut status = [0u8];
-        self.i2c
-            .write_read(self.i2c_addr, &[read_command], &mut status)
-            .await?;
+    /// Sets the delay after each I2C write, in microseconds.
+    pub fn set_write_delay(&mut self, delay_us: u32) -> RelayResult<(), E> {
+        self.update_config(self.config.with_write_delay_us(delay_us))
+    }
+
+    /// Sets the delay for relays to change state, in milliseconds.
+    pub fn set_state_change_delay(&mut self, delay_ms: u32) -> RelayResult<(), E> {
+        self.update_config(self.config.with_state_change_delay_ms(delay_ms))
+    }
+
+    /// Returns the 7-bit I2C address this instance talks to.
+    pub fn address(&self) -> SevenBitAddress {
+        self.i2c_addr
+    }
 
-        Ok(status[0] != Status::Off as u8)
+    /// Consumes the driver and returns the underlying I2C bus and delay.
+    pub fn release(self) -> (T, D) {
+        (self.i2c, self.delay)
     }
 
-    pub async fn set_all_relays_on(&mut self) -> Result<(), E> {
+    async fn read_register(&mut self, register: u8) -> RelayResult<u8, E> {
+        let mut buffer = [0u8];
         self.i2c
-            .write(self.i2c_addr, &[Command::TurnAllOn as u8])
+            .write_read(self.i2c_addr, &[register], &mut buffer)
             .await
+            .map_err(RelayError::I2C)?;
+        Ok(buffer[0])
     }
 
-    pub async fn set_all_relays_off(&mut self) -> Result<(), E> {
+    async fn write_command(&mut self, command: u8) -> RelayResult<(), E> {
         self.i2c
-            .write(self.i2c_addr, &[Command::TurnAllOff as u8])
+            .write(self.i2c_addr, &[command])
             .await
+            .map_err(RelayError::I2C)
     }
 
-    pub async fn get_version(&mut self) -> Result<u8, E> {
-        let mut version = [0u8];
+    async fn write_register(&mut self, register: u8, value: u8) -> RelayResult<(), E> {
         self.i2c
-            .write_read(
-                self.i2c_addr,
-                &[RelayState::SingleFirmwareVersion as u8],
-                &mut version,
-            )
-            .await?;
-        Ok(version[0])
+            .write(self.i2c_addr, &[register, value])
+            .await
+            .map_err(RelayError::I2C)
+    }
+
+    async fn delay_after_write(&mut self) {
+        if self.config.write_delay_us > 0 {
+            self.delay.delay_us(self.config.write_delay_us).await;
+        }
+    }
+
+    async fn wait_for_state_change(&mut self) {
+        if self.config.state_change_delay_ms > 0 {
+            self.delay.delay_ms(self.config.state_change_delay_ms).await;
+        }
+    }
+
+    fn resolve_channel(&self, relay_num: Option<u8>) -> RelayResult<Channel, E> {
+        Channel::resolve(self.board, relay_num, self.config.relay_count)
+    }
+
+    async fn verify_relay_state_if_enabled(
+        &mut self,
+        relay_num: Option<u8>,
+        expected_state: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, E> {
+        if matches!(self.config.verification.mode, VerificationMode::Disabled) {
+            return Ok(VerificationOutcome::Skipped);
+        }
+
+        self.wait_before_verification().await;
+        self.confirm_relay_state(relay_num, expected_state).await
+    }
+
+    /// Verifies every relay in the bank against `expected`; bit `n - 1` is relay `n`.
+    ///
+    /// The verification delay is waited out once for the whole bank.
+    async fn verify_bank_if_enabled(&mut self, expected: u8) -> RelayResult<BankOutcome, E> {
+        let enabled = !matches!(self.config.verification.mode, VerificationMode::Disabled);
+        if enabled {
+            self.wait_before_verification().await;
+        }
+
+        let mut outcome = BankOutcome::default();
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            let relay_outcome = if enabled {
+                let target = RelayStatus::from(expected & (1 << bit));
+                self.confirm_relay_state(channel.relay_num(), target).await?
+            } else {
+                VerificationOutcome::Skipped
+            };
+            outcome.record(bit, relay_outcome);
+        }
+        Ok(outcome)
+    }
+
+    async fn wait_before_verification(&mut self) {
+        let delay_ms = self.config.verification.verification_delay_ms;
+        if delay_ms > 0 {
+            self.delay.delay_ms(delay_ms as u32).await;
+        }
+    }
+
+    /// Reads the relay back until it reports `expected_state`.
+    ///
+    /// There is no clock in `no_std`, so `timeout_ms` is measured against the
+    /// accumulated retry delays rather than wall time.
+    async fn confirm_relay_state(
+        &mut self,
+        relay_num: Option<u8>,
+        expected_state: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, E> {
+        let verification_config = self.config.verification;
+        let mut waited_ms = 0u64;
+        let mut actual_state = None;
+        let mut timed_out = false;
+
+        for attempt in 0..verification_config.max_retries {
+            if waited_ms > verification_config.timeout_ms {
+                timed_out = true;
+                break;
+            }
+
+            match self.get_relay_state(relay_num).await {
+                Ok(state) if state == expected_state => return Ok(VerificationOutcome::Verified),
+                Ok(state) => actual_state = Some(state),
+                Err(_) if attempt < verification_config.max_retries - 1 => {}
+                Err(e) => return Err(e),
+            }
+
+            if attempt < verification_config.max_retries - 1 {
+                self.delay
+                    .delay_ms(verification_config.retry_delay_ms as u32)
+                    .await;
+                waited_ms += verification_config.retry_delay_ms;
+            }
+        }
+
+        match verification_config.mode {
+            VerificationMode::Lenient => {
+                Ok(VerificationOutcome::Warning(VerificationWarning {
+                    relay_num,
+                    expected: expected_state,
+                    actual: actual_state,
+                    attempts: verification_config.max_retries,
+                    timed_out,
+                }))
+            }
+            _ if timed_out => Err(RelayError::VerificationTimeout {
+                relay_num,
+                expected: expected_state,
+                timeout_ms: verification_config.timeout_ms,
+            }),
+            _ => Err(RelayError::VerificationFailed {
+                relay_num,
+                expected: expected_state,
+                attempts: verification_config.max_retries,
+            }),
+        }
+    }
+
+    /// Gets the current status of a specific relay or the single relay.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4 for multi-relay boards, None for single relay boards)
+    pub async fn get_relay_state(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<RelayStatus, E> {
+        let register = self.resolve_channel(relay_num)?.status_register();
+
+        let status = self.read_register(register).await?;
+        self.delay_after_write().await;
+
+        Ok(RelayStatus::from(status))
+    }
+
+    /// Turns a specific relay on.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4 for multi-relay boards, None for single relay boards)
+    pub async fn set_relay_on(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<VerificationOutcome, E> {
+        self.set_relay(relay_num, RelayStatus::On).await
+    }
+
+    /// Turns a specific relay off.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4 for multi-relay boards, None for single relay boards)
+    pub async fn set_relay_off(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<VerificationOutcome, E> {
+        self.set_relay(relay_num, RelayStatus::Off).await
+    }
+
+    async fn set_relay(
+        &mut self,
+        relay_num: Option<u8>,
+        target: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, E> {
+        let channel = self.resolve_channel(relay_num)?;
+        let command = match channel.set_command(target) {
+            Some(command) => Some(command),
+            None => {
+                let current_state = self.get_relay_state(relay_num).await?;
+                (current_state != target).then(|| channel.toggle_command(current_state))
+            }
+        };
+
+        if let Some(command) = command {
+            self.write_command(command).await?;
+            self.delay_after_write().await;
+            self.wait_for_state_change().await;
+        }
+
+        self.verify_relay_state_if_enabled(relay_num, target).await
+    }
+
+    /// Turns a relay on for `duration`, then returns it to its previous state.
+    ///
+    /// The wait goes through the delay provider. If switching on fails the relay
+    /// is still put back. Restoring needs the bus, so a future dropped while the
+    /// relay is on (for example by losing a `select`) leaves it on; let the
+    /// pulse run to completion.
+    ///
+    /// # Arguments
+    /// * `relay_num` - The relay to pulse (None for single relay boards)
+    /// * `duration` - How long the relay stays on
+    pub async fn pulse(
+        &mut self,
+        relay_num: Option<u8>,
+        duration: core::time::Duration,
+    ) -> RelayResult<VerificationOutcome, E> {
+        self.resolve_channel(relay_num)?;
+        let original = self.get_relay_state(relay_num).await?;
+        let on = match self.set_relay(relay_num, RelayStatus::On).await {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                let _ = self.set_relay(relay_num, original).await;
+                return Err(err);
+            }
+        };
+
+        // DelayNs takes u32 microseconds, so long pulses wait in steps
+        let mut remaining_us = duration.as_micros();
+        while remaining_us > 0 {
+            let step = remaining_us.min(u32::MAX as u128) as u32;
+            self.delay.delay_us(step).await;
+            remaining_us -= step as u128;
+        }
+
+        let restored = self.set_relay(relay_num, original).await?;
+        Ok(on.and(restored))
+    }
+
+    /// Toggles a specific relay (turns it on if off, off if on).
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4 for multi-relay boards, None for single relay boards)
+    pub async fn toggle_relay(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<VerificationOutcome, E> {
+        let current_state = self.get_relay_state(relay_num).await?;
+        let expected_state = if current_state == RelayStatus::On {
+            RelayStatus::Off
+        } else {
+            RelayStatus::On
+        };
+
+        let command = self.resolve_channel(relay_num)?.toggle_command(current_state);
+        self.write_command(command).await?;
+        self.delay_after_write().await;
+        self.wait_for_state_change().await;
+
+        self.verify_relay_state_if_enabled(relay_num, expected_state).await
+    }
+
+    /// Turns all relays on.
+    pub async fn set_all_relays_on(&mut self) -> RelayResult<VerificationOutcome, E> {
+        self.set_all_relays(Command::TurnAllOn, RelayStatus::On).await
+    }
+
+    /// Turns all relays off.
+    pub async fn set_all_relays_off(&mut self) -> RelayResult<VerificationOutcome, E> {
+        self.set_all_relays(Command::TurnAllOff, RelayStatus::Off).await
+    }
+
+    async fn set_all_relays(
+        &mut self,
+        command: Command,
+        target: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, E> {
+        // Single relay boards have no all-relays commands
+        if Channel::primary(self.board, self.config.relay_count) == Channel::Single {
+            return self.set_relay(None, target).await;
+        }
+
+        self.write_command(command as u8).await?;
+        self.delay_after_write().await;
+        self.wait_for_state_change().await;
+
+        let expected = match target {
+            RelayStatus::On => u8::MAX,
+            RelayStatus::Off => 0,
+        };
+        Ok(self.verify_bank_if_enabled(expected).await?.overall())
+    }
+
+    /// Reads every relay into a bitmask; bit `n - 1` is relay `n`.
+    ///
+    /// See `QwiicRelay::get_relays` for details.
+    pub async fn get_relays(&mut self) -> RelayResult<u8, E> {
+        let mut mask = 0;
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            let status = self.read_register(channel.status_register()).await?;
+            self.delay_after_write().await;
+            if RelayStatus::from(status) == RelayStatus::On {
+                mask |= 1 << bit;
+            }
+        }
+        Ok(mask)
+    }
+
+    /// Sets every relay from a bitmask with back-to-back writes.
+    ///
+    /// See `QwiicRelay::set_relays` for details.
+    ///
+    /// # Arguments
+    /// * `mask` - Target states; bit `n - 1` is relay `n`
+    pub async fn set_relays(&mut self, mask: u8) -> RelayResult<VerificationOutcome, E> {
+        registers::check_mask(self.board, self.config.relay_count, mask)?;
+        let current = self.get_relays().await?;
+
+        let changed = current ^ mask;
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            if changed & (1 << bit) == 0 {
+                continue;
+            }
+            let target = RelayStatus::from(mask & (1 << bit));
+            let command = channel
+                .set_command(target)
+                .unwrap_or_else(|| channel.toggle_command(RelayStatus::from(current & (1 << bit))));
+            self.write_command(command).await?;
+            self.delay_after_write().await;
+        }
+        if changed != 0 {
+            self.wait_for_state_change().await;
+        }
+
+        Ok(self.verify_bank_if_enabled(mask).await?.overall())
+    }
+
+    /// Toggles all relays (turns them on if off, off if on).
+    ///
+    /// The states are read first so each relay can be verified to have flipped.
+    pub async fn toggle_all_relays(&mut self) -> RelayResult<BankOutcome, E> {
+        let before = self.get_relays().await?;
+        let command = if Channel::primary(self.board, self.config.relay_count) == Channel::Single {
+            // Single relay boards have no toggle-all command
+            Channel::Single.toggle_command(RelayStatus::from(before & 1))
+        } else {
+            Command::ToggleAll as u8
+        };
+
+        self.write_command(command).await?;
+        self.delay_after_write().await;
+        self.wait_for_state_change().await;
+
+        self.verify_bank_if_enabled(!before).await
+    }
+
+    /// Sets a relay's slow PWM duty cycle on a solid state relay board.
+    ///
+    /// See `QwiicRelay::set_pwm` for details.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4)
+    /// * `duty` - Duty cycle from 0 (off) to [`PWM_MAX`] (always on)
+    pub async fn set_pwm(&mut self, relay_num: u8, duty: u8) -> RelayResult<(), E> {
+        let register = self.pwm_register(relay_num)?;
+        if duty > PWM_MAX {
+            return Err(RelayError::InvalidConfiguration("PWM duty cycle is above PWM_MAX"));
+        }
+        self.write_register(register, duty).await?;
+        self.delay_after_write().await;
+        Ok(())
+    }
+
+    /// Reads a relay's slow PWM duty cycle on a solid state relay board.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4)
+    pub async fn get_pwm(&mut self, relay_num: u8) -> RelayResult<u8, E> {
+        let register = self.pwm_register(relay_num)?;
+        let duty = self.read_register(register).await?;
+        self.delay_after_write().await;
+        Ok(duty)
+    }
+
+    fn pwm_register(&self, relay_num: u8) -> RelayResult<u8, E> {
+        registers::pwm_register(self.board, self.i2c_addr, relay_num, self.config.relay_count)
+    }
+
+    /// Gets the firmware version from the relay board.
+    pub async fn get_version(&mut self) -> RelayResult<u8, E> {
+        let version = self.read_register(registers::FIRMWARE_VERSION).await?;
+        self.delay_after_write().await;
+        Ok(version)
+    }
+
+    /// Changes the I2C address of the relay board.
+    ///
+    /// **WARNING**: This permanently changes the I2C address stored in the device's EEPROM.
+    /// See `QwiicRelay::change_i2c_address` for details.
+    ///
+    /// # Arguments
+    /// * `new_address` - The new I2C address (0x08-0x77)
+    pub async fn change_i2c_address(&mut self, new_address: u8) -> RelayResult<(), E> {
+        if !(0x08..=0x77).contains(&new_address) {
+            return Err(RelayError::InvalidI2CAddress(new_address));
+        }
+
+        self.write_register(registers::CHANGE_ADDRESS, new_address).await?;
+
+        // Wait for the device to process the address change
+        self.delay.delay_ms(100).await;
+
+        Ok(())
+    }
+
+    /// Moves the board to a new I2C address and follows it there.
+    ///
+    /// Checks the new address is free, moves the board, retargets this instance
+    /// and confirms with [`QwiicRelayAsync::init`], going back to the old address
+    /// if that fails. See `QwiicRelay::readdress` for details.
+    ///
+    /// # Arguments
+    /// * `new_address` - The new I2C address (0x08-0x77)
+    pub async fn readdress(&mut self, new_address: u8) -> RelayResult<BoardInfo, E> {
+        if !(0x08..=0x77).contains(&new_address) {
+            return Err(RelayError::InvalidI2CAddress(new_address));
+        }
+        let old_address = self.i2c_addr;
+        if new_address == old_address {
+            return self.init().await;
+        }
+
+        let mut buffer = [0u8];
+        match self.i2c.read(new_address, &mut buffer).await {
+            Ok(()) => return Err(RelayError::AddressInUse(new_address)),
+            Err(err) if matches!(err.kind(), i2c::ErrorKind::NoAcknowledge(_)) => {}
+            Err(err) => return Err(RelayError::I2C(err)),
+        }
+
+        self.change_i2c_address(new_address).await?;
+        self.i2c_addr = new_address;
+
+        let error = match self.init().await {
+            Ok(info) => return Ok(info),
+            Err(error) => error,
+        };
+
+        self.i2c_addr = old_address;
+        match self.init().await {
+            Ok(_) => Err(RelayError::AddressChangeFailed {
+                old_address,
+                new_address,
+            }),
+            Err(_) => Err(error),
+        }
     }
 }
 
-// Linux std implementation
-#[cfg(feature = "std")]
 /// Status values returned by the relay board.
+#[cfg(any(feature = "std", feature = "embedded"))]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum RelayStatus {
     Off = 0,
     On = 1,
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "embedded"))]
 impl From<bool> for RelayStatus {
     fn from(value: bool) -> Self {
         if value { RelayStatus::On } else { RelayStatus::Off }
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "embedded"))]
 impl From<RelayStatus> for bool {
     fn from(status: RelayStatus) -> Self {
         status == RelayStatus::On
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "embedded"))]
 impl From<u8> for RelayStatus {
     fn from(value: u8) -> Self {
         if value != 0 { RelayStatus::On } else { RelayStatus::Off }
     }
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "embedded"))]
 impl From<RelayStatus> for u8 {
     fn from(status: RelayStatus) -> Self {
         status as u8
     }
 }
 
-/// Configuration for a Qwiic Relay board.
-#[cfg(feature = "std")]
+/// Configuration for a Qwiic Relay board, shared by the std and async drivers.
+#[cfg(any(feature = "std", feature = "embedded"))]
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct QwiicRelayConfig {
     /// Number of relays on the board (1, 2, or 4).
     pub relay_count: u8,
@@ -273,7 +841,7 @@ pub struct QwiicRelayConfig {
     pub init_delay_ms: u32,
 }
 
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "embedded"))]
 impl QwiicRelayConfig {
     /// Creates a new configuration with the specified number of relays and default timing.
     ///
@@ -289,6 +857,66 @@ impl QwiicRelayConfig {
         }
     }
 
+    /// Creates a configuration tuned for solid state relays.
+    ///
+    /// Uses 5μs write delay, 5ms state change and 100ms init. There is no contact
+    /// bounce, so verification starts almost immediately and gives up quickly.
+    ///
+    /// # Arguments
+    /// * `relay_count` - Number of relays on the board (typically 2 or 4)
+    pub fn for_solid_state(relay_count: u8) -> QwiicRelayConfig {
+        QwiicRelayConfig::for_profile(relay_count, TimingProfile::SolidState)
+    }
+
+    /// Creates a configuration tuned for mechanical relays.
+    ///
+    /// Uses 15μs write delay, 20ms state change and 250ms init. Verification waits
+    /// out contact bounce before the first readback and allows more retries.
+    ///
+    /// # Arguments
+    /// * `relay_count` - Number of relays on the board (typically 1 or 4)
+    pub fn for_mechanical(relay_count: u8) -> QwiicRelayConfig {
+        QwiicRelayConfig::for_profile(relay_count, TimingProfile::Mechanical)
+    }
+
+    /// Creates a configuration for the given timing profile.
+    pub fn for_profile(relay_count: u8, profile: TimingProfile) -> QwiicRelayConfig {
+        QwiicRelayConfig {
+            relay_count,
+            verification: profile.verification(),
+            write_delay_us: profile.write_delay_us(),
+            state_change_delay_ms: profile.state_change_delay_ms(),
+            init_delay_ms: profile.init_delay_ms(),
+        }
+    }
+
+    /// Creates a configuration with custom timing.
+    ///
+    /// Verification is strict and waits `state_change_ms` before the first readback
+    /// and between retries, so it scales with the switching time.
+    ///
+    /// # Arguments
+    /// * `relay_count` - Number of relays on the board
+    /// * `write_delay_us` - Microseconds delay after write operations
+    /// * `state_change_ms` - Milliseconds to wait for state change
+    /// * `init_delay_ms` - Milliseconds to wait during initialization
+    pub fn with_timing(
+        relay_count: u8,
+        write_delay_us: u32,
+        state_change_ms: u32,
+        init_delay_ms: u32,
+    ) -> QwiicRelayConfig {
+        QwiicRelayConfig {
+            relay_count,
+            verification: VerificationConfig::strict()
+                .with_verification_delay(state_change_ms as u64)
+                .with_retry_delay(state_change_ms as u64),
+            write_delay_us,
+            state_change_delay_ms: state_change_ms,
+            init_delay_ms,
+        }
+    }
+
     /// Sets the verification configuration.
     pub fn with_verification(mut self, verification: VerificationConfig) -> QwiicRelayConfig {
         self.verification = verification;
@@ -307,77 +935,317 @@ impl QwiicRelayConfig {
         self
     }
 
-    /// Sets the initialization delay in milliseconds.
-    pub fn with_init_delay_ms(mut self, delay_ms: u32) -> QwiicRelayConfig {
-        self.init_delay_ms = delay_ms;
-        self
+    /// Sets the initialization delay in milliseconds.
+    pub fn with_init_delay_ms(mut self, delay_ms: u32) -> QwiicRelayConfig {
+        self.init_delay_ms = delay_ms;
+        self
+    }
+
+    /// Sets the write delay in microseconds.
+    pub fn set_write_delay_us(&mut self, delay_us: u32) {
+        self.write_delay_us = delay_us;
+    }
+
+    /// Sets the state change delay in milliseconds.
+    pub fn set_state_change_delay_ms(&mut self, delay_ms: u32) {
+        self.state_change_delay_ms = delay_ms;
+    }
+
+    /// Sets the initialization delay in milliseconds.
+    pub fn set_init_delay_ms(&mut self, delay_ms: u32) {
+        self.init_delay_ms = delay_ms;
+    }
+
+    /// Checks the configuration for combinations no board can satisfy.
+    ///
+    /// A known `board` limits the relay count to what the hardware has. Without
+    /// one the address says nothing reliable, since boards can be readdressed,
+    /// so any count up to 16 is accepted.
+    pub(crate) fn validate(&self, board: Option<BoardKind>) -> Result<(), &'static str> {
+        let max_relays = board.map_or(16, BoardKind::relay_count);
+        if self.relay_count == 0 {
+            return Err("relay_count must be at least 1");
+        }
+        if self.relay_count > max_relays {
+            return Err("relay_count exceeds the relays on the board");
+        }
+        if !matches!(self.verification.mode, VerificationMode::Disabled) {
+            if self.verification.max_retries == 0 {
+                return Err("verification needs at least one attempt");
+            }
+            if self.verification.timeout_ms == 0 {
+                return Err("verification timeout must be non-zero");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "embedded"))]
+impl Default for QwiicRelayConfig {
+    /// Creates a default configuration for a quad relay board (4 relays) with standard timing.
+    fn default() -> Self {
+        QwiicRelayConfig::new(4)
+    }
+}
+
+// Linux std implementation
+/// Main interface for controlling a Qwiic Relay board.
+///
+/// The driver works with any blocking embedded-hal 1.0 I2C bus. On Linux,
+/// [`QwiicRelay::new`] opens a `/dev/i2c-*` device through [`LinuxBus`].
+#[cfg(feature = "std")]
+pub struct QwiicRelay<I2C = LinuxBus> {
+    i2c: I2C,
+    i2c_addr: u8,
+    board: Option<BoardKind>,
+    /// The configuration for this relay board.
+    pub config: QwiicRelayConfig,
+    diagnostics: Vec<VerificationWarning>,
+    cache: StateCache,
+    pulses: Option<PulseRegistry>,
+}
+
+/// Maximum number of Lenient-mode warnings kept by [`QwiicRelay::diagnostics`].
+#[cfg(feature = "std")]
+const MAX_DIAGNOSTICS: usize = 64;
+
+#[cfg(feature = "std")]
+impl<I2C> std::fmt::Debug for QwiicRelay<I2C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QwiicRelay")
+            .field("i2c_addr", &self.i2c_addr)
+            .field("board", &self.board)
+            .field("config", &self.config)
+            .field("diagnostics", &self.diagnostics.len())
+            .field("cache", &self.cache)
+            .field("restore_on_signal", &self.pulses.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl QwiicRelay<LinuxBus> {
+    /// Creates a new QwiicRelay instance on a Linux I2C bus.
+    ///
+    /// Waits for the board to answer, for at most `init_delay_ms`, before
+    /// returning (see [`QwiicRelay::init`]).
+    ///
+    /// # Arguments
+    /// * `config` - Configuration for the relay board
+    /// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
+    /// * `i2c_addr` - I2C address of the relay board
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
+    ///
+    /// let config = QwiicRelayConfig::default();
+    /// let mut qwiic_relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
+    /// ```
+    pub fn new(config: QwiicRelayConfig, bus: &str, i2c_addr: u16) -> RelayResult<QwiicRelay> {
+        let i2c_addr = u8::try_from(i2c_addr)
+            .ok()
+            .filter(|addr| *addr <= 0x7F)
+            .ok_or(RelayError::InvalidConfiguration("I2C address is not a 7-bit address"))?;
+        let bus = LinuxBus::new(bus)?;
+        let mut relay = QwiicRelay::from_i2c(config, bus, i2c_addr);
+        relay.init()?;
+        Ok(relay)
+    }
+
+    /// Creates a QwiicRelay for a specific board on a Linux I2C bus.
+    ///
+    /// The address, relay count, command set and timing all come from `kind`,
+    /// so there is no way to pair a board with the wrong relay count. Like
+    /// [`QwiicRelay::new`], this waits for the board to answer.
+    ///
+    /// # Arguments
+    /// * `kind` - The board type
+    /// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
+    /// * `jumper_closed` - Whether the board's address jumper is closed
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadSolidState, "/dev/i2c-1", false).unwrap();
+    /// relay.set_relay_on(Some(3)).unwrap();
+    /// ```
+    pub fn for_board(kind: BoardKind, bus: &str, jumper_closed: bool) -> RelayResult<QwiicRelay> {
+        let bus = LinuxBus::new(bus)?;
+        let mut relay = QwiicRelay::from_board(kind, bus, jumper_closed);
+        relay.init()?;
+        Ok(relay)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I2C: BlockingI2c> QwiicRelay<I2C> {
+    /// Creates a new QwiicRelay instance on an existing embedded-hal I2C bus.
+    ///
+    /// Unlike [`QwiicRelay::new`], this does not talk to the board; call
+    /// [`QwiicRelay::init`] to wait for it to finish booting.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration for the relay board
+    /// * `i2c` - Any bus implementing `embedded_hal::i2c::I2c`
+    /// * `i2c_addr` - 7-bit I2C address of the relay board
+    pub fn from_i2c(config: QwiicRelayConfig, i2c: I2C, i2c_addr: u8) -> QwiicRelay<I2C> {
+        QwiicRelay {
+            i2c,
+            i2c_addr,
+            board: None,
+            config,
+            diagnostics: Vec::new(),
+            cache: StateCache::default(),
+            pulses: None,
+        }
+    }
+
+    /// Creates a QwiicRelay for a specific board on an existing embedded-hal I2C bus.
+    ///
+    /// The address, relay count, command set and timing all come from `kind`.
+    /// Like [`QwiicRelay::from_i2c`], this does not wait for the board to boot.
+    ///
+    /// # Arguments
+    /// * `kind` - The board type
+    /// * `i2c` - Any bus implementing `embedded_hal::i2c::I2c`
+    /// * `jumper_closed` - Whether the board's address jumper is closed
+    pub fn from_board(kind: BoardKind, i2c: I2C, jumper_closed: bool) -> QwiicRelay<I2C> {
+        let mut relay = QwiicRelay::from_i2c(kind.config(), i2c, kind.address(jumper_closed));
+        relay.board = Some(kind);
+        relay
+    }
+
+    /// Returns the board type, if the driver was created for a specific board.
+    pub fn board(&self) -> Option<BoardKind> {
+        self.board
+    }
+
+    /// Waits for the board to answer and identifies it.
+    ///
+    /// Polls a register that is safe to read every 10ms until the board
+    /// acknowledges, for at most `init_delay_ms`. A board that is already up
+    /// answers on the first poll, so startup does not pay the full delay.
+    ///
+    /// That register is the firmware version on a driver created for a single
+    /// relay board. Without a [`BoardKind`] it is the first status register:
+    /// the version register toggles relay 4 on multi relay boards, and a board
+    /// moved to another board's address must not be mistaken for that board.
+    ///
+    /// # Errors
+    /// `DeviceNotFound` if nothing acknowledges the address before the deadline;
+    /// other bus errors are returned as they happen.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, LinuxBus, QwiicRelay};
+    ///
+    /// let bus = LinuxBus::new("/dev/i2c-1").unwrap();
+    /// let mut relay = QwiicRelay::from_board(BoardKind::SingleRelay, bus, false);
+    /// let info = relay.init().unwrap();
+    /// println!("{:?} firmware {:?}", info.kind, info.firmware_version);
+    /// ```
+    pub fn init(&mut self) -> RelayResult<BoardInfo, I2C::Error> {
+        let (kind, register) = registers::probe_target(self.board, self.i2c_addr, self.config.relay_count);
+        let deadline = Duration::from_millis(self.config.init_delay_ms as u64);
+        let start = Instant::now();
+
+        let response = loop {
+            match self.read_register(register) {
+                Ok(response) => break response,
+                Err(RelayError::I2C(err)) if matches!(err.kind(), ErrorKind::NoAcknowledge(_)) => {
+                    if start.elapsed() >= deadline {
+                        return Err(RelayError::DeviceNotFound {
+                            address: self.i2c_addr,
+                            waited_ms: start.elapsed().as_millis() as u64,
+                        });
+                    }
+                    thread::sleep(Duration::from_millis(board::INIT_POLL_INTERVAL_MS as u64));
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        self.delay_after_write();
+
+        Ok(BoardInfo {
+            address: self.i2c_addr,
+            kind,
+            firmware_version: (register == registers::FIRMWARE_VERSION).then_some(response),
+        })
+    }
+
+    /// Replaces the configuration after validating it.
+    ///
+    /// The new configuration is checked as a whole and swapped in at once; if it
+    /// is rejected the current one stays in place. Operations borrow the driver
+    /// mutably, so no operation can observe a half-applied configuration.
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` if the relay count is 0 or exceeds the board's
+    /// relays (16 when no [`BoardKind`] is set), or the verification settings
+    /// can never succeed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
+    ///
+    /// let mut relay = QwiicRelay::new(QwiicRelayConfig::default(), "/dev/i2c-1", 0x08).unwrap();
+    /// relay.update_config(QwiicRelayConfig::for_solid_state(4)).unwrap();
+    /// ```
+    pub fn update_config(&mut self, config: QwiicRelayConfig) -> RelayResult<(), I2C::Error> {
+        config
+            .validate(self.board)
+            .map_err(RelayError::InvalidConfiguration)?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Sets the delay after each I2C write, in microseconds.
+    pub fn set_write_delay(&mut self, delay_us: u32) -> RelayResult<(), I2C::Error> {
+        self.update_config(self.config.with_write_delay_us(delay_us))
     }
 
-    /// Sets the write delay in microseconds.
-    pub fn set_write_delay_us(&mut self, delay_us: u32) {
-        self.write_delay_us = delay_us;
+    /// Sets the delay for relays to change state, in milliseconds.
+    pub fn set_state_change_delay(&mut self, delay_ms: u32) -> RelayResult<(), I2C::Error> {
+        self.update_config(self.config.with_state_change_delay_ms(delay_ms))
     }
 
-    /// Sets the state change delay in milliseconds.
-    pub fn set_state_change_delay_ms(&mut self, delay_ms: u32) {
-        self.state_change_delay_ms = delay_ms;
+    /// Returns the 7-bit I2C address this instance talks to.
+    pub fn address(&self) -> u8 {
+        self.i2c_addr
     }
 
-    /// Sets the initialization delay in milliseconds.
-    pub fn set_init_delay_ms(&mut self, delay_ms: u32) {
-        self.init_delay_ms = delay_ms;
+    /// Consumes the driver and returns the underlying I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
     }
-}
 
-#[cfg(feature = "std")]
-impl Default for QwiicRelayConfig {
-    /// Creates a default configuration for a quad relay board (4 relays) with standard timing.
-    fn default() -> Self {
-        QwiicRelayConfig::new(4)
+    fn read_register(&mut self, register: u8) -> RelayResult<u8, I2C::Error> {
+        let mut buffer = [0u8];
+        let result = self.i2c.write_read(self.i2c_addr, &[register], &mut buffer);
+        self.bus_result(result)?;
+        Ok(buffer[0])
     }
-}
 
-/// Main interface for controlling a Qwiic Relay board.
-#[cfg(feature = "std")]
-pub struct QwiicRelay {
-    dev: LinuxI2CDevice,
-    /// The configuration for this relay board.
-    pub config: QwiicRelayConfig,
-}
-
-#[cfg(feature = "std")]
-impl std::fmt::Debug for QwiicRelay {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("QwiicRelay")
-            .field("config", &self.config)
-            .finish()
+    fn write_command(&mut self, command: u8) -> RelayResult<(), I2C::Error> {
+        let result = self.i2c.write(self.i2c_addr, &[command]);
+        self.bus_result(result)
     }
-}
 
-#[cfg(feature = "std")]
-type VersionResult = Result<u8, RelayError>;
+    fn write_register(&mut self, register: u8, value: u8) -> RelayResult<(), I2C::Error> {
+        let result = self.i2c.write(self.i2c_addr, &[register, value]);
+        self.bus_result(result)
+    }
 
-#[cfg(feature = "std")]
-impl QwiicRelay {
-    /// Creates a new QwiicRelay instance.
-    ///
-    /// # Arguments
-    /// * `config` - Configuration for the relay board
-    /// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
-    /// * `i2c_addr` - I2C address of the relay board
-    ///
-    /// # Examples
-    /// ```no_run
-    /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
-    ///
-    /// let config = QwiicRelayConfig::default();
-    /// let mut qwiic_relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
-    /// ```
-    pub fn new(config: QwiicRelayConfig, bus: &str, i2c_addr: u16) -> RelayResult<QwiicRelay> {
-        let mut dev = LinuxI2CDevice::new(bus, i2c_addr)?;
-        dev.set_slave_address(i2c_addr)?;
-        thread::sleep(Duration::from_millis(config.init_delay_ms as u64));
-        Ok(QwiicRelay { dev, config })
+    /// Wraps a bus error, dropping the cached relay states since a failed
+    /// transfer may or may not have reached the board.
+    fn bus_result(&mut self, result: Result<(), I2C::Error>) -> RelayResult<(), I2C::Error> {
+        result.map_err(|err| {
+            self.cache.invalidate();
+            RelayError::I2C(err)
+        })
     }
 
     fn delay_after_write(&self) {
@@ -398,49 +1266,154 @@ impl QwiicRelay {
         &mut self,
         relay_num: Option<u8>,
         expected_state: RelayStatus,
-    ) -> RelayResult<()> {
-        use verification::VerificationMode;
-        
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
         match self.config.verification.mode {
-            VerificationMode::Disabled => Ok(()),
+            VerificationMode::Disabled => Ok(VerificationOutcome::Skipped),
             VerificationMode::Strict | VerificationMode::Lenient => {
-                let verification_config = self.config.verification;
-                let start_time = Instant::now();
-
-                for attempt in 0..verification_config.max_retries {
-                    if start_time.elapsed() > Duration::from_millis(verification_config.timeout_ms) {
-                        return Err(RelayError::VerificationTimeout {
-                            relay_num,
-                            expected: expected_state,
-                            timeout_ms: verification_config.timeout_ms,
-                        });
-                    }
+                self.wait_before_verification();
+                self.confirm_relay_state(relay_num, expected_state)
+            }
+        }
+    }
 
-                    match self.get_relay_state(relay_num) {
-                        Ok(actual_state) => {
-                            if actual_state == expected_state {
-                                return Ok(());
-                            }
-                        }
-                        Err(_) if attempt < verification_config.max_retries - 1 => {
-                            thread::sleep(Duration::from_millis(verification_config.retry_delay_ms));
-                            continue;
+    /// Verifies every relay in the bank against `expected`; bit `n - 1` is relay `n`.
+    ///
+    /// The relays were switched together, so the verification delay is waited
+    /// out once for the whole bank before each relay is read back.
+    fn verify_bank_if_enabled(&mut self, expected: u8) -> RelayResult<BankOutcome, I2C::Error> {
+        let enabled = !matches!(self.config.verification.mode, VerificationMode::Disabled);
+        if enabled {
+            self.wait_before_verification();
+        }
+
+        let mut outcome = BankOutcome::default();
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            let relay_outcome = if enabled {
+                self.confirm_relay_state(channel.relay_num(), RelayStatus::from(expected & (1 << bit)))?
+            } else {
+                VerificationOutcome::Skipped
+            };
+            outcome.record(bit, relay_outcome);
+        }
+        Ok(outcome)
+    }
+
+    /// Lets the contacts settle before the first readback so bounce isn't
+    /// counted as a failed attempt.
+    fn wait_before_verification(&self) {
+        if self.config.verification.verification_delay_ms > 0 {
+            thread::sleep(self.config.verification.verification_delay());
+        }
+    }
+
+    /// Reads the relay back until it reports `expected_state`, retrying as configured.
+    fn confirm_relay_state(
+        &mut self,
+        relay_num: Option<u8>,
+        expected_state: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        let verification_config = self.config.verification;
+        let start_time = Instant::now();
+        let mut actual_state = None;
+
+        let failure = 'verify: {
+            for attempt in 0..verification_config.max_retries {
+                if start_time.elapsed() > Duration::from_millis(verification_config.timeout_ms) {
+                    break 'verify RelayError::VerificationTimeout {
+                        relay_num,
+                        expected: expected_state,
+                        timeout_ms: verification_config.timeout_ms,
+                    };
+                }
+
+                let state = self
+                    .resolve_channel(relay_num)
+                    .and_then(|channel| self.read_relay_state(channel));
+                match state {
+                    Ok(state) => {
+                        if state == expected_state {
+                            return Ok(VerificationOutcome::Verified);
                         }
-                        Err(e) => return Err(e),
+                        actual_state = Some(state);
                     }
-
-                    if attempt < verification_config.max_retries - 1 {
+                    Err(_) if attempt < verification_config.max_retries - 1 => {
                         thread::sleep(Duration::from_millis(verification_config.retry_delay_ms));
+                        continue;
                     }
+                    Err(e) => return Err(e),
                 }
 
-                Err(RelayError::VerificationFailed {
-                    relay_num,
-                    expected: expected_state,
-                    attempts: verification_config.max_retries,
-                })
+                if attempt < verification_config.max_retries - 1 {
+                    thread::sleep(Duration::from_millis(verification_config.retry_delay_ms));
+                }
+            }
+
+            RelayError::VerificationFailed {
+                relay_num,
+                expected: expected_state,
+                attempts: verification_config.max_retries,
             }
+        };
+
+        self.handle_verification_failure(failure, actual_state)
+    }
+
+    /// Turns a verification failure into an error (Strict) or a recorded warning (Lenient).
+    fn handle_verification_failure(
+        &mut self,
+        failure: RelayError<I2C::Error>,
+        actual: Option<RelayStatus>,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        // The relay is not where the driver put it
+        self.cache.invalidate();
+        if !matches!(self.config.verification.mode, VerificationMode::Lenient) {
+            return Err(failure);
+        }
+
+        let warning = match failure {
+            RelayError::VerificationFailed {
+                relay_num,
+                expected,
+                attempts,
+            } => VerificationWarning {
+                relay_num,
+                expected,
+                actual,
+                attempts,
+                timed_out: false,
+            },
+            RelayError::VerificationTimeout {
+                relay_num, expected, ..
+            } => VerificationWarning {
+                relay_num,
+                expected,
+                actual,
+                attempts: self.config.verification.max_retries,
+                timed_out: true,
+            },
+            other => return Err(other),
+        };
+
+        #[cfg(feature = "log")]
+        log::warn!("qwiic relay 0x{:02X}: {}", self.i2c_addr, warning);
+
+        if self.diagnostics.len() == MAX_DIAGNOSTICS {
+            self.diagnostics.remove(0);
         }
+        self.diagnostics.push(warning);
+        Ok(VerificationOutcome::Warning(warning))
+    }
+
+    /// Returns the verification warnings recorded in Lenient mode, oldest first.
+    ///
+    /// Only the most recent 64 warnings are kept.
+    pub fn diagnostics(&self) -> &[VerificationWarning] {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the recorded verification warnings.
+    pub fn take_diagnostics(&mut self) -> Vec<VerificationWarning> {
+        std::mem::take(&mut self.diagnostics)
     }
 
     /// Gets the current status of a specific relay or the single relay.
@@ -460,23 +1433,28 @@ impl QwiicRelay {
     /// let status = relay.get_relay_state(Some(1)).unwrap();
     /// println!("Relay 1 is {:?}", status);
     /// ```
-    pub fn get_relay_state(&mut self, relay_num: Option<u8>) -> RelayResult<RelayStatus> {
-        let read_command = if let Some(num) = relay_num {
-            if num < 1 || num > self.config.relay_count {
-                return Err(RelayError::InvalidRelayNumber {
-                    relay_num: num,
-                    max_relays: self.config.relay_count,
-                });
-            }
-            0x04 + num
-        } else {
-            RelayState::SingleStatusVersion as u8
-        };
+    pub fn get_relay_state(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<RelayStatus, I2C::Error> {
+        let channel = self.resolve_channel(relay_num)?;
+        self.relay_state(channel)
+    }
+
+    /// A relay's state from the cache when the policy allows, otherwise from the board.
+    fn relay_state(&mut self, channel: Channel) -> RelayResult<RelayStatus, I2C::Error> {
+        match self.cache.lookup(channel) {
+            Some(status) => Ok(status),
+            None => self.read_relay_state(channel),
+        }
+    }
 
-        let status = self.dev.smbus_read_byte_data(read_command)?;
+    /// Reads a relay's state from the board, bypassing the cache, and caches it.
+    pub(crate) fn read_relay_state(&mut self, channel: Channel) -> RelayResult<RelayStatus, I2C::Error> {
+        let status = RelayStatus::from(self.read_register(channel.status_register())?);
         self.delay_after_write();
-        
-        Ok(RelayStatus::from(status))
+        self.cache.record(channel, status);
+        Ok(status)
     }
 
     /// Turns a specific relay on.
@@ -484,6 +1462,10 @@ impl QwiicRelay {
     /// # Arguments
     /// * `relay_num` - Relay number (1-4 for multi-relay boards, None for single relay boards)
     ///
+    /// # Returns
+    /// The verification outcome. In Lenient mode a relay that does not reach the
+    /// expected state yields `VerificationOutcome::Warning` instead of an error.
+    ///
     /// # Examples
     /// ```no_run
     /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
@@ -492,30 +1474,11 @@ impl QwiicRelay {
     /// let mut relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
     /// relay.set_relay_on(Some(1)).unwrap();
     /// ```
-    pub fn set_relay_on(&mut self, relay_num: Option<u8>) -> RelayResult<()> {
-        if let Some(num) = relay_num {
-            if num < 1 || num > self.config.relay_count {
-                return Err(RelayError::InvalidRelayNumber {
-                    relay_num: num,
-                    max_relays: self.config.relay_count,
-                });
-            }
-            
-            let current_state = self.get_relay_state(relay_num)?;
-            if current_state != RelayStatus::On {
-                self.dev
-                    .smbus_write_byte(Command::DualQuadToggleBase as u8 + num)?;
-                self.delay_after_write();
-                self.wait_for_state_change();
-            }
-        } else {
-            self.dev.smbus_write_byte(RelayState::On as u8)?;
-            self.delay_after_write();
-            self.wait_for_state_change();
-        }
-
-        self.verify_relay_state_if_enabled(relay_num, RelayStatus::On)?;
-        Ok(())
+    pub fn set_relay_on(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.set_relay(relay_num, RelayStatus::On)
     }
 
     /// Turns a specific relay off.
@@ -531,30 +1494,39 @@ impl QwiicRelay {
     /// let mut relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
     /// relay.set_relay_off(Some(1)).unwrap();
     /// ```
-    pub fn set_relay_off(&mut self, relay_num: Option<u8>) -> RelayResult<()> {
-        if let Some(num) = relay_num {
-            if num < 1 || num > self.config.relay_count {
-                return Err(RelayError::InvalidRelayNumber {
-                    relay_num: num,
-                    max_relays: self.config.relay_count,
-                });
-            }
-            
-            let current_state = self.get_relay_state(relay_num)?;
-            if current_state != RelayStatus::Off {
-                self.dev
-                    .smbus_write_byte(Command::DualQuadToggleBase as u8 + num)?;
-                self.delay_after_write();
-                self.wait_for_state_change();
+    pub fn set_relay_off(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.set_relay(relay_num, RelayStatus::Off)
+    }
+
+    fn resolve_channel(&self, relay_num: Option<u8>) -> RelayResult<Channel, I2C::Error> {
+        Channel::resolve(self.board, relay_num, self.config.relay_count)
+    }
+
+    fn set_relay(
+        &mut self,
+        relay_num: Option<u8>,
+        target: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        let channel = self.resolve_channel(relay_num)?;
+        let command = match channel.set_command(target) {
+            Some(command) => Some(command),
+            None => {
+                let current_state = self.relay_state(channel)?;
+                (current_state != target).then(|| channel.toggle_command(current_state))
             }
-        } else {
-            self.dev.smbus_write_byte(RelayState::Off as u8)?;
+        };
+
+        if let Some(command) = command {
+            self.write_command(command)?;
+            self.cache.record(channel, target);
             self.delay_after_write();
             self.wait_for_state_change();
         }
 
-        self.verify_relay_state_if_enabled(relay_num, RelayStatus::Off)?;
-        Ok(())
+        self.verify_relay_state_if_enabled(relay_num, target)
     }
 
     /// Toggles a specific relay (turns it on if off, off if on).
@@ -570,52 +1542,31 @@ impl QwiicRelay {
     /// let mut relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
     /// relay.toggle_relay(Some(1)).unwrap();
     /// ```
-    pub fn toggle_relay(&mut self, relay_num: Option<u8>) -> RelayResult<()> {
-        if let Some(num) = relay_num {
-            if num < 1 || num > self.config.relay_count {
-                return Err(RelayError::InvalidRelayNumber {
-                    relay_num: num,
-                    max_relays: self.config.relay_count,
-                });
-            }
-            
-            let current_state = self.get_relay_state(relay_num)?;
-            self.dev
-                .smbus_write_byte(Command::DualQuadToggleBase as u8 + num)?;
-            self.delay_after_write();
-            self.wait_for_state_change();
-
-            let expected_state = if current_state == RelayStatus::On {
-                RelayStatus::Off
-            } else {
-                RelayStatus::On
-            };
-            self.verify_relay_state_if_enabled(relay_num, expected_state)?;
+    pub fn toggle_relay(
+        &mut self,
+        relay_num: Option<u8>,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        let channel = self.resolve_channel(relay_num)?;
+        let current_state = self.relay_state(channel)?;
+        let expected_state = if current_state == RelayStatus::On {
+            RelayStatus::Off
         } else {
-            let current_state = self.get_relay_state(relay_num)?;
-            let toggle_command = if current_state == RelayStatus::On {
-                RelayState::Off as u8
-            } else {
-                RelayState::On as u8
-            };
-            
-            self.dev.smbus_write_byte(toggle_command)?;
-            self.delay_after_write();
-            self.wait_for_state_change();
+            RelayStatus::On
+        };
 
-            let expected_state = if current_state == RelayStatus::On {
-                RelayStatus::Off
-            } else {
-                RelayStatus::On
-            };
-            self.verify_relay_state_if_enabled(relay_num, expected_state)?;
-        }
-        
-        Ok(())
+        self.write_command(channel.toggle_command(current_state))?;
+        self.cache.record(channel, expected_state);
+        self.delay_after_write();
+        self.wait_for_state_change();
+
+        self.verify_relay_state_if_enabled(relay_num, expected_state)
     }
 
     /// Turns all relays on.
     ///
+    /// Every channel is verified; the first Lenient-mode warning, if any, is returned
+    /// and all of them are recorded in [`QwiicRelay::diagnostics`].
+    ///
     /// # Examples
     /// ```no_run
     /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
@@ -624,16 +1575,8 @@ impl QwiicRelay {
     /// let mut relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
     /// relay.set_all_relays_on().unwrap();
     /// ```
-    pub fn set_all_relays_on(&mut self) -> RelayResult<()> {
-        self.dev.smbus_write_byte(Command::TurnAllOn as u8)?;
-        self.delay_after_write();
-        self.wait_for_state_change();
-
-        for relay_num in 1..=self.config.relay_count {
-            self.verify_relay_state_if_enabled(Some(relay_num), RelayStatus::On)?;
-        }
-        
-        Ok(())
+    pub fn set_all_relays_on(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.set_all_relays(Command::TurnAllOn, RelayStatus::On)
     }
 
     /// Turns all relays off.
@@ -646,35 +1589,193 @@ impl QwiicRelay {
     /// let mut relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
     /// relay.set_all_relays_off().unwrap();
     /// ```
-    pub fn set_all_relays_off(&mut self) -> RelayResult<()> {
-        self.dev.smbus_write_byte(Command::TurnAllOff as u8)?;
+    pub fn set_all_relays_off(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.set_all_relays(Command::TurnAllOff, RelayStatus::Off)
+    }
+
+    fn set_all_relays(
+        &mut self,
+        command: Command,
+        target: RelayStatus,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        // Single relay boards have no all-relays commands
+        if Channel::primary(self.board, self.config.relay_count) == Channel::Single {
+            return self.set_relay(None, target);
+        }
+
+        self.write_command(command as u8)?;
+        for (_, channel) in registers::bank(self.board, self.config.relay_count) {
+            self.cache.record(channel, target);
+        }
         self.delay_after_write();
         self.wait_for_state_change();
 
-        for relay_num in 1..=self.config.relay_count {
-            self.verify_relay_state_if_enabled(Some(relay_num), RelayStatus::Off)?;
+        let expected = match target {
+            RelayStatus::On => u8::MAX,
+            RelayStatus::Off => 0,
+        };
+        Ok(self.verify_bank_if_enabled(expected)?.overall())
+    }
+
+    /// Reads every relay into a bitmask; bit `n - 1` is relay `n`.
+    ///
+    /// A single relay board reports its relay as bit 0. Like
+    /// [`QwiicRelay::get_relay_state`], this uses cached states when the
+    /// [`CachePolicy`] allows; [`QwiicRelay::sync`] always reads the board.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// let mask = relay.get_relays().unwrap();
+    /// println!("relay 3 is {}", if mask & 0b0100 != 0 { "on" } else { "off" });
+    /// ```
+    pub fn get_relays(&mut self) -> RelayResult<u8, I2C::Error> {
+        let mut mask = 0;
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            if self.relay_state(channel)? == RelayStatus::On {
+                mask |= 1 << bit;
+            }
         }
-        
-        Ok(())
+        Ok(mask)
+    }
+
+    /// Sets every relay from a bitmask; bit `n - 1` is relay `n`.
+    ///
+    /// The current states are read once, then only the relays that differ are
+    /// switched, with their commands sent back to back so they change together.
+    /// The state change delay and verification then run once for the whole
+    /// bank, and every relay is checked against `mask`.
+    ///
+    /// # Arguments
+    /// * `mask` - Target states; bit `n - 1` is relay `n`
+    ///
+    /// # Errors
+    /// `InvalidRelayNumber` if `mask` has bits above the board's relays.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// relay.set_relays(0b0101).unwrap();
+    /// relay.set_relays(0b1010).unwrap(); // four toggles, sent back to back
+    /// ```
+    pub fn set_relays(&mut self, mask: u8) -> RelayResult<VerificationOutcome, I2C::Error> {
+        registers::check_mask(self.board, self.config.relay_count, mask)?;
+        let current = self.get_relays()?;
+
+        let changed = current ^ mask;
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            if changed & (1 << bit) == 0 {
+                continue;
+            }
+            let target = RelayStatus::from(mask & (1 << bit));
+            let command = channel
+                .set_command(target)
+                .unwrap_or_else(|| channel.toggle_command(RelayStatus::from(current & (1 << bit))));
+            self.write_command(command)?;
+            self.cache.record(channel, target);
+            self.delay_after_write();
+        }
+        if changed != 0 {
+            self.wait_for_state_change();
+        }
+
+        Ok(self.verify_bank_if_enabled(mask)?.overall())
     }
 
     /// Toggles all relays (turns them on if off, off if on).
     ///
+    /// The states are read first, so after the toggle each relay is verified to
+    /// have flipped. In Strict mode the first relay that did not flip returns an
+    /// error; otherwise the outcome of every relay is reported.
+    ///
+    /// # Returns
+    /// The verification outcome of each relay.
+    ///
     /// # Examples
     /// ```no_run
     /// use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
     ///
     /// let config = QwiicRelayConfig::default();
     /// let mut relay = QwiicRelay::new(config, "/dev/i2c-1", 0x08).unwrap();
-    /// relay.toggle_all_relays().unwrap();
+    /// let outcome = relay.toggle_all_relays().unwrap();
+    /// for (relay_num, result) in outcome.iter() {
+    ///     println!("relay {}: {:?}", relay_num, result);
+    /// }
     /// ```
-    pub fn toggle_all_relays(&mut self) -> RelayResult<()> {
-        self.dev.smbus_write_byte(Command::ToggleAll as u8)?;
+    pub fn toggle_all_relays(&mut self) -> RelayResult<BankOutcome, I2C::Error> {
+        let before = self.get_relays()?;
+        let command = if Channel::primary(self.board, self.config.relay_count) == Channel::Single {
+            // Single relay boards have no toggle-all command
+            Channel::Single.toggle_command(RelayStatus::from(before & 1))
+        } else {
+            Command::ToggleAll as u8
+        };
+
+        self.write_command(command)?;
+        for (bit, channel) in registers::bank(self.board, self.config.relay_count) {
+            self.cache.record(channel, RelayStatus::from(!before & (1 << bit)));
+        }
         self.delay_after_write();
         self.wait_for_state_change();
+
+        self.verify_bank_if_enabled(!before)
+    }
+
+    /// Sets a relay's slow PWM duty cycle on a solid state relay board.
+    ///
+    /// The board switches the relay on for `duty / PWM_MAX` of each PWM period,
+    /// which gives proportional control (e.g. of a heater) without an external
+    /// PWM source. A duty of 0 stops the PWM. Only the dual and quad solid state
+    /// boards support this; the board is taken from [`QwiicRelay::board`] or,
+    /// failing that, from the address.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4)
+    /// * `duty` - Duty cycle from 0 (off) to [`PWM_MAX`] (always on)
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` on boards without slow PWM or for a duty above
+    /// `PWM_MAX`; `InvalidRelayNumber` for a relay the board does not have.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, PWM_MAX, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadSolidState, "/dev/i2c-1", false).unwrap();
+    /// // Run the heater on relay 1 at 25%
+    /// relay.set_pwm(1, PWM_MAX / 4).unwrap();
+    /// ```
+    pub fn set_pwm(&mut self, relay_num: u8, duty: u8) -> RelayResult<(), I2C::Error> {
+        let register = self.pwm_register(relay_num)?;
+        if duty > PWM_MAX {
+            return Err(RelayError::InvalidConfiguration("PWM duty cycle is above PWM_MAX"));
+        }
+        self.write_register(register, duty)?;
+        // PWM switches the relay by itself from now on
+        self.cache.invalidate();
+        self.delay_after_write();
         Ok(())
     }
 
+    /// Reads a relay's slow PWM duty cycle on a solid state relay board.
+    ///
+    /// # Arguments
+    /// * `relay_num` - Relay number (1-4)
+    pub fn get_pwm(&mut self, relay_num: u8) -> RelayResult<u8, I2C::Error> {
+        let register = self.pwm_register(relay_num)?;
+        let duty = self.read_register(register)?;
+        self.delay_after_write();
+        Ok(duty)
+    }
+
+    fn pwm_register(&self, relay_num: u8) -> RelayResult<u8, I2C::Error> {
+        registers::pwm_register(self.board, self.i2c_addr, relay_num, self.config.relay_count)
+    }
+
     /// Gets the firmware version from the relay board.
     ///
     /// # Returns
@@ -689,10 +1790,8 @@ impl QwiicRelay {
     /// let version = relay.get_version().unwrap();
     /// println!("Firmware version: {}", version);
     /// ```
-    pub fn get_version(&mut self) -> VersionResult {
-        let version = self
-            .dev
-            .smbus_read_byte_data(RelayState::SingleFirmwareVersion as u8)?;
+    pub fn get_version(&mut self) -> RelayResult<u8, I2C::Error> {
+        let version = self.read_register(registers::FIRMWARE_VERSION)?;
         self.delay_after_write();
         Ok(version)
     }
@@ -724,22 +1823,88 @@ impl QwiicRelay {
     /// - The device must be power cycled to use the new address
     /// - Ensure the new address doesn't conflict with other devices
     /// - Reserved I2C addresses (0x00-0x07, 0x78-0x7F) should be avoided
-    pub fn change_i2c_address(&mut self, new_address: u8) -> RelayResult<()> {
+    /// - This instance keeps talking to the old address; use
+    ///   [`QwiicRelay::readdress`] to check, move and follow the board in one step
+    pub fn change_i2c_address(&mut self, new_address: u8) -> RelayResult<(), I2C::Error> {
         // Validate the new address
-        if new_address < 0x08 || new_address > 0x77 {
+        if !(0x08..=0x77).contains(&new_address) {
             return Err(RelayError::InvalidI2CAddress(new_address));
         }
         
-        const CHANGE_ADDRESS_COMMAND: u8 = 0xC7;
-        
         // Send the change address command
-        self.dev.smbus_write_byte_data(CHANGE_ADDRESS_COMMAND, new_address)?;
+        self.write_register(registers::CHANGE_ADDRESS, new_address)?;
         
         // Wait for the device to process the address change
         thread::sleep(Duration::from_millis(100));
-        
+
+        // Whatever answers at the old address later may be another board
+        self.cache.invalidate();
+
         Ok(())
     }
+
+    /// Moves the board to a new I2C address and follows it there.
+    ///
+    /// The new address is checked to be free first, then the board is told to
+    /// move, this instance is retargeted and [`QwiicRelay::init`] confirms the
+    /// board answers at the new address. The confirmation probes the board this
+    /// driver was created for, or failing that its relay count, so a board moved
+    /// onto another board type's address is never read as that type. If the
+    /// confirmation fails the instance goes back to the old address.
+    ///
+    /// **WARNING**: Like [`QwiicRelay::change_i2c_address`], this is stored in the
+    /// device's EEPROM.
+    ///
+    /// # Arguments
+    /// * `new_address` - The new I2C address (0x08-0x77)
+    ///
+    /// # Errors
+    /// * `InvalidI2CAddress` if `new_address` is outside 0x08-0x77
+    /// * `AddressInUse` if another device already answers at `new_address`
+    /// * `AddressChangeFailed` if the board is still at the old address
+    /// * `DeviceNotFound` if the board answers at neither address
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// relay.readdress(0x10).unwrap();
+    /// assert_eq!(relay.address(), 0x10);
+    /// ```
+    pub fn readdress(&mut self, new_address: u8) -> RelayResult<BoardInfo, I2C::Error> {
+        if !(0x08..=0x77).contains(&new_address) {
+            return Err(RelayError::InvalidI2CAddress(new_address));
+        }
+        let old_address = self.i2c_addr;
+        if new_address == old_address {
+            return self.init();
+        }
+
+        let mut buffer = [0u8];
+        match self.i2c.read(new_address, &mut buffer) {
+            Ok(()) => return Err(RelayError::AddressInUse(new_address)),
+            Err(err) if matches!(err.kind(), ErrorKind::NoAcknowledge(_)) => {}
+            Err(err) => return Err(RelayError::I2C(err)),
+        }
+
+        self.change_i2c_address(new_address)?;
+        self.i2c_addr = new_address;
+
+        let error = match self.init() {
+            Ok(info) => return Ok(info),
+            Err(error) => error,
+        };
+
+        self.i2c_addr = old_address;
+        match self.init() {
+            Ok(_) => Err(RelayError::AddressChangeFailed {
+                old_address,
+                new_address,
+            }),
+            Err(_) => Err(error),
+        }
+    }
 }
 
 #[cfg(all(test, feature = "std"))]
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt;
+
+use embedded_hal::i2c::{self, ErrorKind, NoAcknowledgeSource, Operation, SevenBitAddress};
+use i2cdev::core::{I2CMessage, I2CTransfer};
+use i2cdev::linux::{LinuxI2CBus, LinuxI2CError, LinuxI2CMessage};
+use nix::errno::Errno;
+
+/// An embedded-hal I2C bus backed by a Linux `/dev/i2c-*` character device.
+///
+/// Every transaction is issued as a single `I2C_RDWR` ioctl, so a write followed
+/// by a read uses a repeated start just like an SMBus "read byte data" would.
+pub struct LinuxBus {
+    bus: LinuxI2CBus,
+}
+
+impl fmt::Debug for LinuxBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinuxBus").finish_non_exhaustive()
+    }
+}
+
+impl LinuxBus {
+    /// Opens the I2C bus at the given path (e.g., "/dev/i2c-1").
+    pub fn new(path: &str) -> Result<LinuxBus, LinuxBusError> {
+        Ok(LinuxBus {
+            bus: LinuxI2CBus::new(path)?,
+        })
+    }
+}
+
+/// Error returned by [`LinuxBus`], wrapping the underlying `i2cdev` error.
+#[derive(Debug)]
+pub struct LinuxBusError(pub LinuxI2CError);
+
+impl fmt::Display for LinuxBusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for LinuxBusError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<LinuxI2CError> for LinuxBusError {
+    fn from(err: LinuxI2CError) -> Self {
+        LinuxBusError(err)
+    }
+}
+
+impl i2c::Error for LinuxBusError {
+    fn kind(&self) -> ErrorKind {
+        let errno = match &self.0 {
+            LinuxI2CError::Nix(err) => err.as_errno(),
+            LinuxI2CError::Io(err) => err.raw_os_error().map(Errno::from_i32),
+        };
+        match errno {
+            // The kernel reports a missing ACK as ENXIO or EREMOTEIO depending on the adapter.
+            Some(Errno::ENXIO) | Some(Errno::EREMOTEIO) => {
+                ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)
+            }
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl i2c::ErrorType for LinuxBus {
+    type Error = LinuxBusError;
+}
+
+impl i2c::I2c for LinuxBus {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut messages: Vec<LinuxI2CMessage> = operations
+            .iter_mut()
+            .map(|operation| match operation {
+                Operation::Read(buffer) => LinuxI2CMessage::read(buffer),
+                Operation::Write(bytes) => LinuxI2CMessage::write(bytes),
+            })
+            .map(|message| message.with_address(address as u16))
+            .collect();
+
+        if messages.is_empty() {
+            return Ok(());
+        }
+
+        self.bus.transfer(&mut messages)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,328 @@
+use std::fmt;
+use std::panic;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::{QwiicRelay, RelayResult, RelayStatus, VerificationOutcome};
+
+/// Sleeps until `deadline`, returning early if `cancel` fires or its handle is dropped.
+fn wait_until(deadline: Instant, cancel: Option<&Receiver<()>>) {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match cancel {
+        Some(cancel) => {
+            let _ = cancel.recv_timeout(remaining);
+        }
+        None => thread::sleep(remaining),
+    }
+}
+
+/// The pulses running under one [`PulseRegistry`], by id.
+#[derive(Default)]
+struct Active {
+    next_id: u64,
+    pulses: Vec<(u64, Sender<()>)>,
+}
+
+#[derive(Default)]
+struct Shared {
+    active: Mutex<Active>,
+    restored: Condvar,
+}
+
+/// Keeps track of running pulses so an application can end them all at once.
+///
+/// The library installs no signal handlers. An application that wants relays
+/// put back on Ctrl-C or SIGTERM hands a registry to each driver with
+/// [`QwiicRelay::restore_on_signal`] and calls [`PulseRegistry::restore_all`]
+/// from its own handler thread (for example `ctrlc` or a `signal-hook`
+/// iterator) before exiting. It must not be called from inside a raw signal
+/// handler, since it locks and blocks.
+///
+/// Clones share the same set of pulses.
+///
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use qwiic_relay_rs::{BoardKind, PulseRegistry, QwiicRelay};
+///
+/// let pulses = PulseRegistry::new();
+/// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+/// relay.restore_on_signal(&pulses);
+///
+/// // In the application's signal handling thread:
+/// let on_signal = pulses.clone();
+/// std::thread::spawn(move || {
+///     // ... wait for the signal
+///     on_signal.restore_all();
+///     std::process::exit(130);
+/// });
+///
+/// relay.pulse(Some(1), Duration::from_secs(30)).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct PulseRegistry {
+    shared: Arc<Shared>,
+}
+
+impl fmt::Debug for PulseRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PulseRegistry")
+            .field("active", &self.lock().pulses.len())
+            .finish()
+    }
+}
+
+impl PulseRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ends every running pulse and waits until each relay has been restored.
+    ///
+    /// Returns the number of pulses that were ended. Pulses started while this
+    /// runs are left alone.
+    pub fn restore_all(&self) -> usize {
+        let mut active = self.lock();
+        let ended: Vec<u64> = active.pulses.iter().map(|&(id, _)| id).collect();
+        for (_, cancel) in &active.pulses {
+            let _ = cancel.send(());
+        }
+        while active.pulses.iter().any(|(id, _)| ended.contains(id)) {
+            active = self.shared.restored.wait(active).unwrap_or_else(PoisonError::into_inner);
+        }
+        ended.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Active> {
+        self.shared.active.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn register(&self, cancel: Sender<()>) -> Registration {
+        let mut active = self.lock();
+        let id = active.next_id;
+        active.next_id += 1;
+        active.pulses.push((id, cancel));
+        Registration {
+            registry: self.clone(),
+            id,
+        }
+    }
+}
+
+/// Removes a pulse from its registry once the relay is back, however the pulse ended.
+struct Registration {
+    registry: PulseRegistry,
+    id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.registry.lock().pulses.retain(|&(id, _)| id != self.id);
+        self.registry.shared.restored.notify_all();
+    }
+}
+
+/// Puts a pulsed relay back into its original state when dropped.
+///
+/// This is what restores the relay if anything between switching it on and
+/// switching it back panics.
+struct PulseGuard<'a, I2C: BlockingI2c> {
+    relay: &'a mut QwiicRelay<I2C>,
+    relay_num: Option<u8>,
+    original: RelayStatus,
+    armed: bool,
+}
+
+impl<I2C: BlockingI2c> PulseGuard<'_, I2C> {
+    fn restore(mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.armed = false;
+        self.relay.set_relay(self.relay_num, self.original)
+    }
+}
+
+impl<I2C: BlockingI2c> Drop for PulseGuard<'_, I2C> {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = self.relay.set_relay(self.relay_num, self.original);
+        }
+    }
+}
+
+/// Switches the relay on, runs `hold` and restores the relay, returning what `hold` returned.
+pub(crate) fn hold_on<I2C: BlockingI2c, T>(
+    relay: &mut QwiicRelay<I2C>,
+    relay_num: Option<u8>,
+    hold: impl FnOnce() -> T,
+) -> RelayResult<(VerificationOutcome, T), I2C::Error> {
+    let original = relay.get_relay_state(relay_num)?;
+
+    let guard = PulseGuard {
+        relay,
+        relay_num,
+        original,
+        armed: true,
+    };
+    let on = guard.relay.set_relay(relay_num, RelayStatus::On)?;
+    let held = hold();
+    let restored = guard.restore()?;
+
+    Ok((on.and(restored), held))
+}
+
+/// Switches the relay on, waits out `duration` (or a cancel) and restores it.
+fn run_pulse<I2C: BlockingI2c>(
+    relay: &mut QwiicRelay<I2C>,
+    relay_num: Option<u8>,
+    duration: Duration,
+    cancel: Option<&Receiver<()>>,
+) -> RelayResult<VerificationOutcome, I2C::Error> {
+    hold_on(relay, relay_num, || wait_until(Instant::now() + duration, cancel)).map(|(outcome, ())| outcome)
+}
+
+impl<I2C: BlockingI2c> QwiicRelay<I2C> {
+    /// Turns a relay on for `duration`, then returns it to its previous state.
+    ///
+    /// A panic while the relay is on unwinds through a guard that switches it
+    /// back. A relay that was already on stays on. No signal handlers are
+    /// installed; to restore the relay on Ctrl-C, register the driver with
+    /// [`QwiicRelay::restore_on_signal`] and end the pulse through the
+    /// [`PulseRegistry`] from the application's handler.
+    ///
+    /// # Arguments
+    /// * `relay_num` - The relay to pulse (None for single relay boards)
+    /// * `duration` - How long the relay stays on
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// // Release a door strike for two seconds
+    /// relay.pulse(Some(1), Duration::from_secs(2)).unwrap();
+    /// ```
+    pub fn pulse(
+        &mut self,
+        relay_num: Option<u8>,
+        duration: Duration,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.resolve_channel(relay_num)?;
+        let (cancel, cancelled) = mpsc::channel();
+        let registration = self.pulses.as_ref().map(|pulses| pulses.register(cancel));
+        run_pulse(self, relay_num, duration, registration.as_ref().map(|_| &cancelled))
+    }
+
+    /// Lets `registry` end this driver's pulses from outside, for example from a signal handler.
+    ///
+    /// Every later [`QwiicRelay::pulse`] and [`QwiicRelay::start_pulse`] is
+    /// registered for as long as its relay is switched; see [`PulseRegistry`].
+    pub fn restore_on_signal(&mut self, registry: &PulseRegistry) {
+        self.pulses = Some(registry.clone());
+    }
+}
+
+impl<I2C> QwiicRelay<I2C>
+where
+    I2C: BlockingI2c + Send + 'static,
+    I2C::Error: Send,
+{
+    /// Starts a pulse on a background thread and returns immediately.
+    ///
+    /// The driver moves into the returned [`PulseHandle`] for the length of the
+    /// pulse and comes back from [`PulseHandle::wait`] or [`PulseHandle::cancel`].
+    /// Dropping the handle, including while the caller's thread unwinds from a
+    /// panic, cancels the pulse and restores the relay.
+    ///
+    /// # Arguments
+    /// * `relay_num` - The relay to pulse (None for single relay boards)
+    /// * `duration` - How long the relay stays on
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// let pulse = relay.start_pulse(Some(2), Duration::from_secs(30)).unwrap();
+    /// // The pump is primed early
+    /// let (relay, result) = pulse.cancel();
+    /// result.unwrap();
+    /// ```
+    pub fn start_pulse(
+        mut self,
+        relay_num: Option<u8>,
+        duration: Duration,
+    ) -> RelayResult<PulseHandle<I2C>, I2C::Error> {
+        self.resolve_channel(relay_num)?;
+        let (cancel, cancelled) = mpsc::channel();
+        let registration = self.pulses.as_ref().map(|pulses| pulses.register(cancel.clone()));
+        let worker = thread::spawn(move || {
+            let _registration = registration;
+            let result = run_pulse(&mut self, relay_num, duration, Some(&cancelled));
+            (self, result)
+        });
+        Ok(PulseHandle {
+            worker: Some(worker),
+            cancel,
+        })
+    }
+}
+
+/// The driver handed back by a finished pulse, with the pulse's result.
+type Finished<I2C> = (
+    QwiicRelay<I2C>,
+    RelayResult<VerificationOutcome, <I2C as embedded_hal::i2c::ErrorType>::Error>,
+);
+
+/// A pulse running on a background thread, from [`QwiicRelay::start_pulse`].
+///
+/// Dropping the handle cancels the pulse and waits for the relay to be restored.
+pub struct PulseHandle<I2C: BlockingI2c> {
+    worker: Option<JoinHandle<Finished<I2C>>>,
+    cancel: Sender<()>,
+}
+
+impl<I2C: BlockingI2c> fmt::Debug for PulseHandle<I2C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PulseHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+impl<I2C: BlockingI2c> PulseHandle<I2C> {
+    /// Returns true once the relay has been restored.
+    pub fn is_finished(&self) -> bool {
+        self.worker.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// Waits for the pulse to finish and returns the driver with the pulse's result.
+    pub fn wait(mut self) -> Finished<I2C> {
+        self.join()
+    }
+
+    /// Ends the pulse now, restoring the relay, and returns the driver with the pulse's result.
+    pub fn cancel(mut self) -> Finished<I2C> {
+        let _ = self.cancel.send(());
+        self.join()
+    }
+
+    fn join(&mut self) -> Finished<I2C> {
+        let worker = self.worker.take().expect("pulse joined twice");
+        worker.join().unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+}
+
+impl<I2C: BlockingI2c> Drop for PulseHandle<I2C> {
+    fn drop(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            let _ = self.cancel.send(());
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{BoardKind, CommandSet, Command, RelayError, RelayResult, RelayState, RelayStatus};
+
+/// Register sent to move a board to a new I2C address.
+pub(crate) const CHANGE_ADDRESS: u8 = 0xC7;
+
+/// Register holding the firmware version.
+///
+/// Only meaningful on single relay boards; on quad boards 0x04 is the toggle
+/// command for relay 4.
+pub(crate) const FIRMWARE_VERSION: u8 = RelayState::SingleFirmwareVersion as u8;
+
+/// Full-scale slow PWM duty cycle on solid state relay boards.
+///
+/// A relay with duty `d` is on for `d / PWM_MAX` of each PWM period; 0 leaves
+/// it to the on/off commands.
+pub const PWM_MAX: u8 = 120;
+
+/// Resolves `relay_num` to its slow PWM duty register (0x10-0x13).
+///
+/// The board comes from the driver or else from its address; only solid state
+/// boards have PWM registers.
+pub(crate) fn pwm_register<E>(
+    board: Option<BoardKind>,
+    i2c_addr: u8,
+    relay_num: u8,
+    relay_count: u8,
+) -> RelayResult<u8, E> {
+    let board = board.or_else(|| BoardKind::from_address(i2c_addr));
+    if !board.is_some_and(BoardKind::supports_pwm) {
+        return Err(RelayError::InvalidConfiguration(
+            "slow PWM needs a solid state relay board",
+        ));
+    }
+    match Channel::resolve(board, Some(relay_num), relay_count)? {
+        Channel::Relay(num) => Ok(Command::RelayOnePwm as u8 + num - 1),
+        Channel::Single => Err(RelayError::InvalidConfiguration(
+            "slow PWM needs a solid state relay board",
+        )),
+    }
+}
+
+/// The board type `init()` reports and the register it polls.
+///
+/// The firmware version register is the toggle command for relay 4 on multi
+/// relay boards, so it is only read when the board is known to be a single relay
+/// board. Without a board the first status register is polled, which is
+/// read-only on every board, and the address only names the board when its
+/// relay count agrees with `relay_count`.
+pub(crate) fn probe_target(
+    board: Option<BoardKind>,
+    i2c_addr: u8,
+    relay_count: u8,
+) -> (Option<BoardKind>, u8) {
+    match board {
+        Some(kind) => (board, Channel::primary(Some(kind), relay_count).probe_register()),
+        None => (
+            BoardKind::from_address(i2c_addr).filter(|kind| kind.relay_count() == relay_count),
+            Channel::primary(None, relay_count).status_register(),
+        ),
+    }
+}
+
+/// The channels of a whole board, each with its bit in a relay mask.
+///
+/// Bit `n - 1` is relay `n`; a single relay board is just bit 0.
+pub(crate) fn bank(board: Option<BoardKind>, relay_count: u8) -> impl Iterator<Item = (u8, Channel)> {
+    let single = Channel::primary(board, relay_count) == Channel::Single;
+    let len = if single { 1 } else { relay_count.min(8) };
+    (0..len).map(move |bit| {
+        let channel = if single { Channel::Single } else { Channel::Relay(bit + 1) };
+        (bit, channel)
+    })
+}
+
+/// Checks that `mask` has no bits above the bank's relays.
+pub(crate) fn check_mask<E>(
+    board: Option<BoardKind>,
+    relay_count: u8,
+    mask: u8,
+) -> RelayResult<(), E> {
+    let len = bank(board, relay_count).count() as u8;
+    let highest = 8 - mask.leading_zeros() as u8;
+    if highest > len {
+        return Err(RelayError::InvalidRelayNumber {
+            relay_num: highest,
+            max_relays: len,
+        });
+    }
+    Ok(())
+}
+
+/// One relay channel, resolved against the board's command set.
+///
+/// Single relay boards switch with 0x00/0x01 and report their state at 0x05.
+/// Dual and quad boards toggle relay `n` with command `n` and report it at `0x04 + n`.
+/// Both drivers go through this map so they address the same registers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Channel {
+    Single,
+    Relay(u8),
+}
+
+impl Channel {
+    /// Maps the public `relay_num` argument (None for single relay boards) to a channel.
+    pub(crate) fn new(relay_num: Option<u8>) -> Channel {
+        match relay_num {
+            Some(num) => Channel::Relay(num),
+            None => Channel::Single,
+        }
+    }
+
+    /// Validates `relay_num` and maps it to a channel.
+    ///
+    /// When the board is known its command set decides: single relay boards accept
+    /// `None` or `Some(1)`, multi relay boards need a relay number. Without a board,
+    /// `None` selects the single relay command set and `Some(n)` is checked against
+    /// `relay_count`.
+    pub(crate) fn resolve<E>(
+        board: Option<BoardKind>,
+        relay_num: Option<u8>,
+        relay_count: u8,
+    ) -> RelayResult<Channel, E> {
+        match (board.map(BoardKind::command_set), relay_num) {
+            (Some(CommandSet::Single), None | Some(1)) => Ok(Channel::Single),
+            (Some(CommandSet::Multi), None) => Err(RelayError::InvalidConfiguration(
+                "a relay number is required for multi relay boards",
+            )),
+            (_, Some(num)) if num < 1 || num > relay_count => {
+                Err(RelayError::InvalidRelayNumber {
+                    relay_num: num,
+                    max_relays: relay_count,
+                })
+            }
+            _ => Ok(Channel::new(relay_num)),
+        }
+    }
+
+    /// The channel a whole-board operation such as probing or calibration uses.
+    ///
+    /// That is the single relay on single relay boards and relay 1 otherwise.
+    /// Without a board, a relay count of 1 selects the single relay command set.
+    pub(crate) fn primary(board: Option<BoardKind>, relay_count: u8) -> Channel {
+        let command_set = board.map(BoardKind::command_set).unwrap_or(if relay_count == 1 {
+            CommandSet::Single
+        } else {
+            CommandSet::Multi
+        });
+        match command_set {
+            CommandSet::Single => Channel::Single,
+            CommandSet::Multi => Channel::Relay(1),
+        }
+    }
+
+    /// The channel's bit in a relay mask; bit `n - 1` is relay `n`.
+    #[cfg(feature = "std")]
+    pub(crate) fn bit(self) -> u8 {
+        match self {
+            Channel::Single => 0,
+            Channel::Relay(num) => num - 1,
+        }
+    }
+
+    /// The public `relay_num` argument that selects this channel.
+    pub(crate) fn relay_num(self) -> Option<u8> {
+        match self {
+            Channel::Single => None,
+            Channel::Relay(num) => Some(num),
+        }
+    }
+
+    /// Register that can be read to check the board is there without side effects.
+    ///
+    /// Single relay boards report their firmware version; on multi relay boards
+    /// that register is the toggle command for relay 4, so the status register
+    /// is read instead.
+    pub(crate) fn probe_register(self) -> u8 {
+        match self {
+            Channel::Single => FIRMWARE_VERSION,
+            Channel::Relay(_) => self.status_register(),
+        }
+    }
+
+    /// Register to read for the channel's current state.
+    pub(crate) fn status_register(self) -> u8 {
+        match self {
+            Channel::Single => RelayState::SingleStatusVersion as u8,
+            Channel::Relay(num) => Command::RelayOneStatus as u8 + num - 1,
+        }
+    }
+
+    /// Command that sets the channel to `target` directly, if the board supports one.
+    ///
+    /// Multi relay boards only toggle, so they return `None` and the caller has to
+    /// read the current state and use [`Channel::toggle_command`].
+    pub(crate) fn set_command(self, target: RelayStatus) -> Option<u8> {
+        match (self, target) {
+            (Channel::Single, RelayStatus::On) => Some(RelayState::On as u8),
+            (Channel::Single, RelayStatus::Off) => Some(RelayState::Off as u8),
+            (Channel::Relay(_), _) => None,
+        }
+    }
+
+    /// Command that flips the channel away from `current`.
+    pub(crate) fn toggle_command(self, current: RelayStatus) -> u8 {
+        match self {
+            Channel::Single if current == RelayStatus::On => RelayState::Off as u8,
+            Channel::Single => RelayState::On as u8,
+            Channel::Relay(num) => Command::DualQuadToggleBase as u8 + num,
+        }
+    }
+}
@@ -0,0 +1,600 @@
+//! Relay sequences: ordered steps run against a [`QwiicRelay`].
+//!
+//! A [`Sequence`] is built in code or parsed from a small line-based text
+//! format, so a cycle can be changed without recompiling:
+//!
+//! ```text
+//! # Irrigation: three zones, twice
+//! all off
+//! repeat 2
+//!   on 1
+//!   wait 10m
+//!   off 1
+//!   pulse 2 30s
+//!   toggle 3
+//!   wait 500ms
+//!   toggle 3
+//! end
+//! ```
+//!
+//! Each line is one step; `#` starts a comment. A relay number may be left out
+//! on single relay boards (`on`, `pulse 2s`). Durations take `ns`, `us`, `ms`,
+//! `s`, `m` or `h`. `repeat N` runs the lines up to the matching `end` N times;
+//! `repeat 0` skips them.
+//!
+//! # Examples
+//! ```
+//! use std::time::Duration;
+//! use qwiic_relay_rs::sequence::Sequence;
+//! use qwiic_relay_rs::sim::{SimBus, SimulatedBoard};
+//! use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
+//!
+//! let bus = SimBus::new().with_board(SimulatedBoard::quad());
+//! let mut relay = QwiicRelay::from_i2c(QwiicRelayConfig::new(4), bus.clone(), 0x6D);
+//!
+//! let sequence: Sequence = "on 1\nwait 5ms\noff 1\non 2".parse().unwrap();
+//! assert_eq!(sequence, Sequence::new().on(1).wait(Duration::from_millis(5)).off(1).on(2));
+//!
+//! sequence.run(&mut relay).unwrap();
+//! assert_eq!(bus.relay_state(0x6D, 2), Some(true));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::pulse;
+use crate::{QwiicRelay, RelayResult, VerificationOutcome};
+
+/// One step of a [`Sequence`].
+///
+/// Relay numbers follow the driver's convention: `None` addresses the relay
+/// of a single relay board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// Turn a relay on.
+    On(Option<u8>),
+    /// Turn a relay off.
+    Off(Option<u8>),
+    /// Toggle a relay.
+    Toggle(Option<u8>),
+    /// Turn a relay on for a time, then restore it (see [`QwiicRelay::pulse`]).
+    Pulse(Option<u8>, Duration),
+    /// Wait before the next step.
+    Wait(Duration),
+    /// Turn all relays on.
+    AllOn,
+    /// Turn all relays off.
+    AllOff,
+    /// Run the nested steps `times` times.
+    Repeat { times: u32, steps: Vec<Step> },
+}
+
+impl Step {
+    /// Number of relay and wait steps this step expands to.
+    fn len(&self) -> usize {
+        match self {
+            Step::Repeat { times, steps } => (*times as usize).saturating_mul(steps_len(steps)),
+            _ => 1,
+        }
+    }
+}
+
+fn steps_len(steps: &[Step]) -> usize {
+    steps.iter().map(Step::len).fold(0, usize::saturating_add)
+}
+
+/// An ordered list of relay steps.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sequence {
+    steps: Vec<Step>,
+}
+
+impl Sequence {
+    /// Creates an empty sequence.
+    pub fn new() -> Sequence {
+        Sequence::default()
+    }
+
+    /// Appends a step.
+    pub fn step(mut self, step: Step) -> Sequence {
+        self.steps.push(step);
+        self
+    }
+
+    /// Appends a step that turns a relay on.
+    pub fn on(self, relay_num: impl Into<Option<u8>>) -> Sequence {
+        self.step(Step::On(relay_num.into()))
+    }
+
+    /// Appends a step that turns a relay off.
+    pub fn off(self, relay_num: impl Into<Option<u8>>) -> Sequence {
+        self.step(Step::Off(relay_num.into()))
+    }
+
+    /// Appends a step that toggles a relay.
+    pub fn toggle(self, relay_num: impl Into<Option<u8>>) -> Sequence {
+        self.step(Step::Toggle(relay_num.into()))
+    }
+
+    /// Appends a step that pulses a relay on for `duration`.
+    pub fn pulse(self, relay_num: impl Into<Option<u8>>, duration: Duration) -> Sequence {
+        self.step(Step::Pulse(relay_num.into(), duration))
+    }
+
+    /// Appends a wait.
+    pub fn wait(self, duration: Duration) -> Sequence {
+        self.step(Step::Wait(duration))
+    }
+
+    /// Appends a step that turns all relays on.
+    pub fn all_on(self) -> Sequence {
+        self.step(Step::AllOn)
+    }
+
+    /// Appends a step that turns all relays off.
+    pub fn all_off(self) -> Sequence {
+        self.step(Step::AllOff)
+    }
+
+    /// Appends a loop that runs the steps built by `build` `times` times.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use qwiic_relay_rs::sequence::Sequence;
+    ///
+    /// let blink = Sequence::new().repeat(3, |s| s.toggle(1).wait(Duration::from_millis(250)));
+    /// assert_eq!(blink.len(), 6);
+    /// ```
+    pub fn repeat(self, times: u32, build: impl FnOnce(Sequence) -> Sequence) -> Sequence {
+        let steps = build(Sequence::new()).steps;
+        self.step(Step::Repeat { times, steps })
+    }
+
+    /// The top-level steps.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Number of steps a run executes, with loops expanded.
+    pub fn len(&self) -> usize {
+        steps_len(&self.steps)
+    }
+
+    /// Returns true if a run executes no steps.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Runs the sequence to completion.
+    pub fn run<I2C: BlockingI2c>(
+        &self,
+        relay: &mut QwiicRelay<I2C>,
+    ) -> RelayResult<SequenceOutcome, I2C::Error> {
+        self.run_with(relay, &SequenceControl::new(), |_| {})
+    }
+
+    /// Runs the sequence under `control`, reporting each finished step to `on_progress`.
+    ///
+    /// Pausing takes effect between steps and during waits and pulses; a paused
+    /// wait or pulse resumes with the time it had left, and a paused pulse holds
+    /// its relay on. Cancelling ends the run at the next step, or immediately
+    /// during a wait or pulse; a cancelled pulse still restores its relay. The
+    /// first relay error ends the run.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::thread;
+    /// use qwiic_relay_rs::sequence::{Sequence, SequenceControl};
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay};
+    ///
+    /// let mut relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// let sequence: Sequence = std::fs::read_to_string("irrigation.seq").unwrap().parse().unwrap();
+    ///
+    /// let control = SequenceControl::new();
+    /// let stop = control.clone();
+    /// thread::spawn(move || {
+    ///     // e.g. on a rain sensor
+    ///     stop.cancel();
+    /// });
+    ///
+    /// sequence
+    ///     .run_with(&mut relay, &control, |progress| {
+    ///         println!("{}/{}: {}", progress.completed, progress.total, progress.step);
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn run_with<I2C: BlockingI2c>(
+        &self,
+        relay: &mut QwiicRelay<I2C>,
+        control: &SequenceControl,
+        mut on_progress: impl FnMut(&StepProgress<'_>),
+    ) -> RelayResult<SequenceOutcome, I2C::Error> {
+        let mut run = Run {
+            relay,
+            control,
+            on_progress: &mut on_progress,
+            completed: 0,
+            total: self.len(),
+        };
+        if run.steps(&self.steps)? {
+            Ok(SequenceOutcome::Completed)
+        } else {
+            Ok(SequenceOutcome::Cancelled {
+                completed: run.completed,
+            })
+        }
+    }
+}
+
+/// How a sequence run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceOutcome {
+    /// Every step ran.
+    Completed,
+    /// The run was cancelled after `completed` steps.
+    Cancelled { completed: usize },
+}
+
+/// Progress reported after each step of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepProgress<'a> {
+    /// Steps finished so far, including this one.
+    pub completed: usize,
+    /// Steps in the whole run, with loops expanded.
+    pub total: usize,
+    /// The step that just finished.
+    pub step: &'a Step,
+    /// The verification result for steps that switch relays.
+    pub outcome: Option<VerificationOutcome>,
+}
+
+struct Run<'a, I2C, F> {
+    relay: &'a mut QwiicRelay<I2C>,
+    control: &'a SequenceControl,
+    on_progress: &'a mut F,
+    completed: usize,
+    total: usize,
+}
+
+impl<I2C: BlockingI2c, F: FnMut(&StepProgress<'_>)> Run<'_, I2C, F> {
+    /// Runs `steps`, returning false if the run was cancelled.
+    fn steps(&mut self, steps: &[Step]) -> RelayResult<bool, I2C::Error> {
+        for step in steps {
+            if !self.control.checkpoint() {
+                return Ok(false);
+            }
+            let outcome = match step {
+                Step::Repeat { times, steps } => {
+                    for _ in 0..*times {
+                        if !self.steps(steps)? {
+                            return Ok(false);
+                        }
+                    }
+                    continue;
+                }
+                Step::Wait(duration) => {
+                    if !self.control.wait(*duration) {
+                        return Ok(false);
+                    }
+                    None
+                }
+                Step::On(relay_num) => Some(self.relay.set_relay_on(*relay_num)?),
+                Step::Off(relay_num) => Some(self.relay.set_relay_off(*relay_num)?),
+                Step::Toggle(relay_num) => Some(self.relay.toggle_relay(*relay_num)?),
+                Step::Pulse(relay_num, duration) => {
+                    self.relay.resolve_channel(*relay_num)?;
+                    let control = self.control;
+                    let (outcome, finished) = pulse::hold_on(self.relay, *relay_num, || control.wait(*duration))?;
+                    if !finished {
+                        return Ok(false);
+                    }
+                    Some(outcome)
+                }
+                Step::AllOn => Some(self.relay.set_all_relays_on()?),
+                Step::AllOff => Some(self.relay.set_all_relays_off()?),
+            };
+
+            self.completed += 1;
+            (self.on_progress)(&StepProgress {
+                completed: self.completed,
+                total: self.total,
+                step,
+                outcome,
+            });
+        }
+        Ok(true)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControlState {
+    Running,
+    Paused,
+    Cancelled,
+}
+
+/// Pauses, resumes or cancels a running sequence from another thread.
+///
+/// Clones share state, so keep one and pass another to
+/// [`Sequence::run_with`]. Cancelling is final: a cancelled control ends any
+/// later run straight away.
+#[derive(Debug, Clone)]
+pub struct SequenceControl {
+    shared: Arc<(Mutex<ControlState>, Condvar)>,
+}
+
+impl Default for SequenceControl {
+    fn default() -> Self {
+        SequenceControl::new()
+    }
+}
+
+impl SequenceControl {
+    /// Creates a control in the running state.
+    pub fn new() -> SequenceControl {
+        SequenceControl {
+            shared: Arc::new((Mutex::new(ControlState::Running), Condvar::new())),
+        }
+    }
+
+    /// Holds the run before its next step, freezing any wait in progress.
+    pub fn pause(&self) {
+        self.transition(ControlState::Paused);
+    }
+
+    /// Lets a paused run continue.
+    pub fn resume(&self) {
+        self.transition(ControlState::Running);
+    }
+
+    /// Ends the run.
+    pub fn cancel(&self) {
+        let mut state = self.lock();
+        *state = ControlState::Cancelled;
+        self.shared.1.notify_all();
+    }
+
+    /// Returns true while paused.
+    pub fn is_paused(&self) -> bool {
+        *self.lock() == ControlState::Paused
+    }
+
+    /// Returns true once cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.lock() == ControlState::Cancelled
+    }
+
+    fn transition(&self, to: ControlState) {
+        let mut state = self.lock();
+        if *state != ControlState::Cancelled {
+            *state = to;
+            self.shared.1.notify_all();
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ControlState> {
+        self.shared.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Blocks while paused; returns false if cancelled.
+    fn checkpoint(&self) -> bool {
+        let mut state = self.lock();
+        while *state == ControlState::Paused {
+            state = self.shared.1.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        *state == ControlState::Running
+    }
+
+    /// Waits for `duration` of running time; returns false if cancelled.
+    fn wait(&self, duration: Duration) -> bool {
+        let mut remaining = duration;
+        let mut state = self.lock();
+        loop {
+            match *state {
+                ControlState::Cancelled => return false,
+                ControlState::Paused => {
+                    state = self.shared.1.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+                }
+                ControlState::Running if remaining.is_zero() => return true,
+                ControlState::Running => {
+                    let started = Instant::now();
+                    state = self
+                        .shared
+                        .1
+                        .wait_timeout(state, remaining)
+                        .map(|(state, _)| state)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner().0);
+                    remaining = remaining.saturating_sub(started.elapsed());
+                }
+            }
+        }
+    }
+}
+
+/// Error returned when sequence text cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceParseError {
+    /// 1-based line number of the problem.
+    pub line: usize,
+    /// What is wrong with the line.
+    pub message: String,
+}
+
+impl fmt::Display for SequenceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for SequenceParseError {}
+
+impl FromStr for Sequence {
+    type Err = SequenceParseError;
+
+    fn from_str(text: &str) -> Result<Sequence, SequenceParseError> {
+        // Each open `repeat` keeps its line number, count and the steps collected so far
+        let mut open: Vec<(usize, u32, Vec<Step>)> = Vec::new();
+        let mut steps = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            let error = |message: String| SequenceParseError {
+                line: number,
+                message,
+            };
+            let line = line.split('#').next().unwrap_or_default().trim().to_ascii_lowercase();
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            let step = match words.as_slice() {
+                [] => continue,
+                ["repeat", count] => {
+                    let times = count
+                        .parse::<u32>()
+                        .map_err(|_| error(format!("repeat count `{}` is not a number", count)))?;
+                    open.push((number, times, std::mem::take(&mut steps)));
+                    continue;
+                }
+                ["end"] => {
+                    let (_, times, outer) = open
+                        .pop()
+                        .ok_or_else(|| error("`end` without a matching `repeat`".to_string()))?;
+                    let body = std::mem::replace(&mut steps, outer);
+                    Step::Repeat { times, steps: body }
+                }
+                ["all", "on"] => Step::AllOn,
+                ["all", "off"] => Step::AllOff,
+                ["on"] => Step::On(None),
+                ["off"] => Step::Off(None),
+                ["toggle"] => Step::Toggle(None),
+                ["on", relay] => Step::On(Some(parse_relay(relay).map_err(error)?)),
+                ["off", relay] => Step::Off(Some(parse_relay(relay).map_err(error)?)),
+                ["toggle", relay] => Step::Toggle(Some(parse_relay(relay).map_err(error)?)),
+                ["pulse", duration] => Step::Pulse(None, parse_duration(duration).map_err(error)?),
+                ["pulse", relay, duration] => Step::Pulse(
+                    Some(parse_relay(relay).map_err(error)?),
+                    parse_duration(duration).map_err(error)?,
+                ),
+                ["wait", duration] => Step::Wait(parse_duration(duration).map_err(error)?),
+                _ => return Err(error(format!("unrecognised step `{}`", line))),
+            };
+            steps.push(step);
+        }
+
+        if let Some((line, _, _)) = open.pop() {
+            return Err(SequenceParseError {
+                line,
+                message: "`repeat` without a matching `end`".to_string(),
+            });
+        }
+        Ok(Sequence { steps })
+    }
+}
+
+fn parse_relay(word: &str) -> Result<u8, String> {
+    word.parse::<u8>()
+        .ok()
+        .filter(|relay_num| *relay_num > 0)
+        .ok_or_else(|| format!("`{}` is not a relay number", word))
+}
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Units of the text format, largest first, with their length in nanoseconds.
+const UNITS: [(&str, u128); 6] = [
+    ("h", 3600 * NANOS_PER_SEC),
+    ("m", 60 * NANOS_PER_SEC),
+    ("s", NANOS_PER_SEC),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+fn parse_duration(word: &str) -> Result<Duration, String> {
+    let split = word.find(|c: char| !c.is_ascii_digit()).unwrap_or(word.len());
+    let (value, unit) = word.split_at(split);
+    let value: u128 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a duration", word))?;
+    let (_, nanos_per_unit) = UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .ok_or_else(|| format!("`{}` needs a unit of ns, us, ms, s, m or h", word))?;
+    value
+        .checked_mul(*nanos_per_unit)
+        .and_then(|nanos| {
+            let secs = u64::try_from(nanos / NANOS_PER_SEC).ok()?;
+            Some(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+        })
+        .ok_or_else(|| format!("`{}` is too long", word))
+}
+
+/// Formats a duration in the largest unit that represents it exactly.
+struct DurationText(Duration);
+
+impl fmt::Display for DurationText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        if nanos == 0 {
+            return write!(f, "0ms");
+        }
+        let (unit, nanos_per_unit) = UNITS
+            .iter()
+            .find(|(_, nanos_per_unit)| nanos % nanos_per_unit == 0)
+            .unwrap_or(&("ns", 1));
+        write!(f, "{}{}", nanos / nanos_per_unit, unit)
+    }
+}
+
+/// Formats an optional relay number with a leading space, or nothing.
+struct RelayText(Option<u8>);
+
+impl fmt::Display for RelayText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(relay_num) => write!(f, " {}", relay_num),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    /// Formats the step in the text format; loops span several lines.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::On(relay_num) => write!(f, "on{}", RelayText(*relay_num)),
+            Step::Off(relay_num) => write!(f, "off{}", RelayText(*relay_num)),
+            Step::Toggle(relay_num) => write!(f, "toggle{}", RelayText(*relay_num)),
+            Step::Pulse(relay_num, duration) => {
+                write!(f, "pulse{} {}", RelayText(*relay_num), DurationText(*duration))
+            }
+            Step::Wait(duration) => write!(f, "wait {}", DurationText(*duration)),
+            Step::AllOn => write!(f, "all on"),
+            Step::AllOff => write!(f, "all off"),
+            Step::Repeat { times, steps } => {
+                writeln!(f, "repeat {}", times)?;
+                for step in steps {
+                    for line in step.to_string().lines() {
+                        writeln!(f, "  {}", line)?;
+                    }
+                }
+                write!(f, "end")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Sequence {
+    /// Formats the sequence in the text format [`Sequence::from_str`] reads.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::{BankOutcome, LinuxBus, QwiicRelay, RelayResult, RelayStatus, VerificationOutcome};
+
+/// A clonable handle to one relay board, for use from several threads.
+///
+/// Every call locks the board for its whole duration, so a read-then-write
+/// such as [`SharedRelay::toggle_relay`] cannot interleave with another
+/// thread's. Use [`SharedRelay::with`] to make a sequence of calls atomic.
+///
+/// A thread that panics while holding the lock does not poison the handle for
+/// the others; the cached relay states are dropped instead, since the panic
+/// may have left them out of date.
+///
+/// # Examples
+/// ```no_run
+/// use std::thread;
+/// use qwiic_relay_rs::{BoardKind, QwiicRelay, SharedRelay};
+///
+/// let relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+/// let shared = SharedRelay::new(relay);
+///
+/// let watchdog = shared.clone();
+/// thread::spawn(move || watchdog.set_all_relays_off());
+/// shared.toggle_relay(Some(1)).unwrap();
+/// ```
+pub struct SharedRelay<I2C = LinuxBus> {
+    relay: Arc<Mutex<QwiicRelay<I2C>>>,
+}
+
+impl<I2C> Clone for SharedRelay<I2C> {
+    fn clone(&self) -> Self {
+        SharedRelay {
+            relay: Arc::clone(&self.relay),
+        }
+    }
+}
+
+impl<I2C> fmt::Debug for SharedRelay<I2C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedRelay")
+            .field("handles", &Arc::strong_count(&self.relay))
+            .finish()
+    }
+}
+
+impl<I2C: BlockingI2c> From<QwiicRelay<I2C>> for SharedRelay<I2C> {
+    fn from(relay: QwiicRelay<I2C>) -> Self {
+        SharedRelay::new(relay)
+    }
+}
+
+impl<I2C: BlockingI2c> SharedRelay<I2C> {
+    /// Wraps a driver so it can be shared between threads.
+    pub fn new(relay: QwiicRelay<I2C>) -> SharedRelay<I2C> {
+        SharedRelay {
+            relay: Arc::new(Mutex::new(relay)),
+        }
+    }
+
+    /// Runs `f` with the board locked, so no other handle can use it in between.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use qwiic_relay_rs::{BoardKind, QwiicRelay, RelayStatus, SharedRelay};
+    ///
+    /// let relay = QwiicRelay::for_board(BoardKind::QuadRelay, "/dev/i2c-1", false).unwrap();
+    /// let shared = SharedRelay::new(relay);
+    /// // Interlock: relay 2 may only come on while relay 1 is off
+    /// shared.with(|relay| {
+    ///     if relay.get_relay_state(Some(1))? == RelayStatus::Off {
+    ///         relay.set_relay_on(Some(2))?;
+    ///     }
+    ///     Ok::<_, qwiic_relay_rs::RelayError>(())
+    /// }).unwrap();
+    /// ```
+    pub fn with<R>(&self, f: impl FnOnce(&mut QwiicRelay<I2C>) -> R) -> R {
+        f(&mut self.lock())
+    }
+
+    /// Returns the driver if this is the last handle to it.
+    pub fn try_unwrap(self) -> Result<QwiicRelay<I2C>, SharedRelay<I2C>> {
+        match Arc::try_unwrap(self.relay) {
+            Ok(relay) => Ok(relay.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())),
+            Err(relay) => Err(SharedRelay { relay }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QwiicRelay<I2C>> {
+        match self.relay.lock() {
+            Ok(relay) => relay,
+            Err(poisoned) => {
+                self.relay.clear_poison();
+                let mut relay = poisoned.into_inner();
+                relay.invalidate_cache();
+                relay
+            }
+        }
+    }
+
+    /// See [`QwiicRelay::get_relay_state`].
+    pub fn get_relay_state(&self, relay_num: Option<u8>) -> RelayResult<RelayStatus, I2C::Error> {
+        self.lock().get_relay_state(relay_num)
+    }
+
+    /// See [`QwiicRelay::set_relay_on`].
+    pub fn set_relay_on(&self, relay_num: Option<u8>) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().set_relay_on(relay_num)
+    }
+
+    /// See [`QwiicRelay::set_relay_off`].
+    pub fn set_relay_off(&self, relay_num: Option<u8>) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().set_relay_off(relay_num)
+    }
+
+    /// See [`QwiicRelay::toggle_relay`]. The state read and the toggle happen under one lock.
+    pub fn toggle_relay(&self, relay_num: Option<u8>) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().toggle_relay(relay_num)
+    }
+
+    /// See [`QwiicRelay::set_all_relays_on`].
+    pub fn set_all_relays_on(&self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().set_all_relays_on()
+    }
+
+    /// See [`QwiicRelay::set_all_relays_off`].
+    pub fn set_all_relays_off(&self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().set_all_relays_off()
+    }
+
+    /// See [`QwiicRelay::toggle_all_relays`].
+    pub fn toggle_all_relays(&self) -> RelayResult<BankOutcome, I2C::Error> {
+        self.lock().toggle_all_relays()
+    }
+
+    /// See [`QwiicRelay::get_relays`].
+    pub fn get_relays(&self) -> RelayResult<u8, I2C::Error> {
+        self.lock().get_relays()
+    }
+
+    /// See [`QwiicRelay::set_relays`].
+    pub fn set_relays(&self, mask: u8) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().set_relays(mask)
+    }
+
+    /// See [`QwiicRelay::pulse`]. Other handles wait until the relay is restored.
+    pub fn pulse(
+        &self,
+        relay_num: Option<u8>,
+        duration: Duration,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.lock().pulse(relay_num, duration)
+    }
+}
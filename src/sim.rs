@@ -0,0 +1,525 @@
+//! A simulated Qwiic Relay board for hardware-free testing.
+//!
+//! [`SimBus`] is a virtual I2C bus implementing `embedded_hal::i2c::I2c` (and
+//! `embedded_hal_async::i2c::I2c` with the `embedded` feature). Any number of
+//! [`SimulatedBoard`]s can be attached to it; each one emulates the SparkFun
+//! register map at the register level, so the real drivers can be exercised
+//! end to end without `/dev/i2c-*`.
+//!
+//! The bus is a cheap, clonable handle: give one clone to the driver and keep
+//! another to inspect or preload relay states from the test.
+//!
+//! Boards can also be scripted to misbehave with a [`Fault`], which makes the
+//! driver's retry, verification and timeout paths deterministic to test.
+//!
+//! # Examples
+//! ```
+//! use qwiic_relay_rs::sim::{SimBus, SimulatedBoard};
+//! use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig, RelayStatus};
+//!
+//! let bus = SimBus::new().with_board(SimulatedBoard::quad());
+//! let mut relay = QwiicRelay::from_i2c(QwiicRelayConfig::new(4), bus.clone(), 0x6D);
+//!
+//! relay.set_relay_on(Some(2)).unwrap();
+//! assert_eq!(relay.get_relay_state(Some(2)).unwrap(), RelayStatus::On);
+//! assert_eq!(bus.relay_state(0x6D, 2), Some(true));
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use embedded_hal::i2c::{self, ErrorKind, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+use crate::{BoardKind, Command, CommandSet, RelayState};
+
+const CHANGE_ADDRESS_COMMAND: u8 = 0xC7;
+
+/// Value returned when reading a register the board does not implement.
+const UNMAPPED_REGISTER: u8 = 0xFF;
+
+/// A scripted misbehaviour of a simulated board.
+///
+/// Transaction and read counts are 1-based and counted from the moment the
+/// fault is injected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// NACK the `nth` transaction addressed to the board, once.
+    Nack { nth: usize },
+    /// Hold a relay at `on` regardless of the commands it receives.
+    StuckRelay { relay_num: u8, on: bool },
+    /// Keep reporting a relay's previous state for `reads` status reads after it switches.
+    DelayedState { reads: u32 },
+    /// XOR the `nth` status byte read from the board with `mask`, once.
+    CorruptStatus { nth: usize, mask: u8 },
+    /// Acknowledge address change commands but keep the current address.
+    RejectAddressChange,
+}
+
+/// Register-level emulation of a single Qwiic Relay board.
+#[derive(Debug, Clone)]
+pub struct SimulatedBoard {
+    kind: BoardKind,
+    address: u8,
+    firmware_version: u8,
+    relays: [bool; 4],
+    pwm: [u8; 4],
+    register: u8,
+    reported: [bool; 4],
+    stale_reads: [u32; 4],
+    switching_delay: Duration,
+    switched_at: [Option<Instant>; 4],
+    transactions: usize,
+    status_reads: usize,
+    faults: Vec<Fault>,
+}
+
+impl SimulatedBoard {
+    /// Creates a board of the given kind at its factory default address, all relays off.
+    pub fn new(kind: BoardKind) -> SimulatedBoard {
+        SimulatedBoard {
+            kind,
+            address: kind.default_address(),
+            firmware_version: 1,
+            relays: [false; 4],
+            pwm: [0; 4],
+            register: UNMAPPED_REGISTER,
+            reported: [false; 4],
+            stale_reads: [0; 4],
+            switching_delay: Duration::ZERO,
+            switched_at: [None; 4],
+            transactions: 0,
+            status_reads: 0,
+            faults: Vec::new(),
+        }
+    }
+
+    /// A Qwiic Single Relay at 0x18.
+    pub fn single() -> SimulatedBoard {
+        SimulatedBoard::new(BoardKind::SingleRelay)
+    }
+
+    /// A Qwiic Dual Solid State Relay at 0x0A.
+    pub fn dual_solid_state() -> SimulatedBoard {
+        SimulatedBoard::new(BoardKind::DualSolidState)
+    }
+
+    /// A Qwiic Quad Relay at 0x6D.
+    pub fn quad() -> SimulatedBoard {
+        SimulatedBoard::new(BoardKind::QuadRelay)
+    }
+
+    /// A Qwiic Quad Solid State Relay at 0x08.
+    pub fn quad_solid_state() -> SimulatedBoard {
+        SimulatedBoard::new(BoardKind::QuadSolidState)
+    }
+
+    /// Places the board at a different I2C address (e.g., a jumpered one).
+    pub fn at_address(mut self, address: u8) -> SimulatedBoard {
+        self.address = address;
+        self
+    }
+
+    /// Sets the value reported by the firmware version register.
+    pub fn with_firmware_version(mut self, version: u8) -> SimulatedBoard {
+        self.firmware_version = version;
+        self
+    }
+
+    /// Presets the state of a relay (1-based).
+    pub fn with_relay_state(mut self, relay_num: u8, on: bool) -> SimulatedBoard {
+        self.set_relay_state(relay_num, on);
+        self
+    }
+
+    /// Models the time a relay needs to change state.
+    ///
+    /// Status reads within `delay` of a switch report the previous state, which
+    /// makes the driver's timing delays observable in tests.
+    pub fn with_switching_delay(mut self, delay: Duration) -> SimulatedBoard {
+        self.switching_delay = delay;
+        self
+    }
+
+    /// Adds a scripted fault to the board.
+    pub fn with_fault(mut self, fault: Fault) -> SimulatedBoard {
+        self.inject(fault);
+        self
+    }
+
+    /// Adds a scripted fault to the board.
+    pub fn inject(&mut self, fault: Fault) {
+        // Store counted faults with absolute positions so they fire exactly once
+        let fault = match fault {
+            Fault::Nack { nth } => Fault::Nack {
+                nth: self.transactions + nth,
+            },
+            Fault::CorruptStatus { nth, mask } => Fault::CorruptStatus {
+                nth: self.status_reads + nth,
+                mask,
+            },
+            other => other,
+        };
+        self.faults.push(fault);
+        self.apply_stuck_relays();
+    }
+
+    /// Removes all scripted faults; relays already reporting stale state catch up immediately.
+    pub fn clear_faults(&mut self) {
+        self.faults.clear();
+        self.stale_reads = [0; 4];
+    }
+
+    /// The board type this board emulates.
+    pub fn kind(&self) -> BoardKind {
+        self.kind
+    }
+
+    /// The I2C address the board currently answers on.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Returns the state of a relay (1-based), or `None` if the board has no such relay.
+    pub fn relay_state(&self, relay_num: u8) -> Option<bool> {
+        self.relay_index(relay_num).map(|index| self.relays[index])
+    }
+
+    /// Sets the state of a relay (1-based); out-of-range relays are ignored.
+    pub fn set_relay_state(&mut self, relay_num: u8, on: bool) {
+        if let Some(index) = self.relay_index(relay_num) {
+            self.relays[index] = on;
+            self.reported[index] = on;
+            self.stale_reads[index] = 0;
+            self.switched_at[index] = None;
+        }
+    }
+
+    /// Slow PWM duty of a relay (1-based), if the board supports slow PWM.
+    pub fn pwm(&self, relay_num: u8) -> Option<u8> {
+        if !self.kind.supports_pwm() {
+            return None;
+        }
+        self.relay_index(relay_num).map(|index| self.pwm[index])
+    }
+
+    fn relay_index(&self, relay_num: u8) -> Option<usize> {
+        (1..=self.kind.relay_count())
+            .contains(&relay_num)
+            .then(|| relay_num as usize - 1)
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), SimError> {
+        self.transactions += 1;
+        let transactions = self.transactions;
+        let nacked = self
+            .faults
+            .iter()
+            .any(|fault| matches!(fault, Fault::Nack { nth } if *nth == transactions));
+        if nacked {
+            self.faults
+                .retain(|fault| !matches!(fault, Fault::Nack { nth } if *nth == transactions));
+            return Err(SimError::nack());
+        }
+        Ok(())
+    }
+
+    fn handle_write(&mut self, bytes: &[u8]) {
+        let before = self.relays;
+        self.apply_command(bytes);
+        self.apply_stuck_relays();
+
+        let delay = self.faults.iter().find_map(|fault| match fault {
+            Fault::DelayedState { reads } => Some(*reads),
+            _ => None,
+        });
+        for (index, was_on) in before.into_iter().enumerate() {
+            if was_on == self.relays[index] {
+                continue;
+            }
+            match delay {
+                Some(reads) if reads > 0 => {
+                    self.reported[index] = was_on;
+                    self.stale_reads[index] = reads;
+                }
+                _ if !self.switching_delay.is_zero() => {
+                    self.reported[index] = was_on;
+                    self.switched_at[index] = Some(Instant::now());
+                }
+                _ => self.reported[index] = self.relays[index],
+            }
+        }
+    }
+
+    fn apply_stuck_relays(&mut self) {
+        for fault in &self.faults {
+            if let Fault::StuckRelay { relay_num, on } = *fault {
+                if let Some(index) = self.relay_index(relay_num) {
+                    self.relays[index] = on;
+                    self.reported[index] = on;
+                }
+            }
+        }
+    }
+
+    fn apply_command(&mut self, bytes: &[u8]) {
+        let Some((&command, payload)) = bytes.split_first() else {
+            return;
+        };
+
+        if command == CHANGE_ADDRESS_COMMAND {
+            let rejected = self.faults.contains(&Fault::RejectAddressChange);
+            if let Some(&new_address) = payload.first() {
+                if (0x08..=0x77).contains(&new_address) && !rejected {
+                    self.address = new_address;
+                }
+            }
+            return;
+        }
+
+        self.register = command;
+        match self.kind.command_set() {
+            CommandSet::Single => match command {
+                c if c == RelayState::Off as u8 => self.relays[0] = false,
+                c if c == RelayState::On as u8 => self.relays[0] = true,
+                _ => {}
+            },
+            CommandSet::Multi => match command {
+                c if (Command::ToggleRelayOne as u8..=Command::ToggleRelayFour as u8)
+                    .contains(&c) =>
+                {
+                    if let Some(index) = self.relay_index(c) {
+                        self.relays[index] = !self.relays[index];
+                    }
+                }
+                c if c == Command::TurnAllOff as u8 => self.set_all(|_| false),
+                c if c == Command::TurnAllOn as u8 => self.set_all(|_| true),
+                c if c == Command::ToggleAll as u8 => self.set_all(|on| !on),
+                c if self.pwm_index(c).is_some() => {
+                    if let (Some(index), Some(&duty)) = (self.pwm_index(c), payload.first()) {
+                        self.pwm[index] = duty;
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn set_all(&mut self, f: impl Fn(bool) -> bool) {
+        let count = self.kind.relay_count() as usize;
+        for relay in &mut self.relays[..count] {
+            *relay = f(*relay);
+        }
+    }
+
+    fn read_register(&mut self) -> u8 {
+        match self.kind.command_set() {
+            CommandSet::Single => match self.register {
+                r if r == RelayState::SingleFirmwareVersion as u8 => self.firmware_version,
+                r if r == RelayState::SingleStatusVersion as u8 => self.read_status(0),
+                _ => UNMAPPED_REGISTER,
+            },
+            CommandSet::Multi => match self.register {
+                r if (Command::RelayOneStatus as u8..=Command::RelayFourStatus as u8)
+                    .contains(&r) =>
+                {
+                    match self.relay_index(r - Command::RelayOneStatus as u8 + 1) {
+                        Some(index) => self.read_status(index),
+                        None => UNMAPPED_REGISTER,
+                    }
+                }
+                r => match self.pwm_index(r) {
+                    Some(index) => self.pwm[index],
+                    None => UNMAPPED_REGISTER,
+                },
+            },
+        }
+    }
+
+    /// Relay index for a slow PWM register on solid state boards.
+    fn pwm_index(&self, register: u8) -> Option<usize> {
+        if !self.kind.supports_pwm() {
+            return None;
+        }
+        let offset = register.checked_sub(Command::RelayOnePwm as u8)?;
+        self.relay_index(offset + 1)
+    }
+
+    fn read_status(&mut self, index: usize) -> u8 {
+        let switching = self.switched_at[index]
+            .is_some_and(|switched_at| switched_at.elapsed() < self.switching_delay);
+        let mut status = if self.stale_reads[index] > 0 {
+            self.stale_reads[index] -= 1;
+            self.reported[index] as u8
+        } else if switching {
+            self.reported[index] as u8
+        } else {
+            self.reported[index] = self.relays[index];
+            self.switched_at[index] = None;
+            self.relays[index] as u8
+        };
+
+        self.status_reads += 1;
+        let status_reads = self.status_reads;
+        if let Some(position) = self.faults.iter().position(
+            |fault| matches!(fault, Fault::CorruptStatus { nth, .. } if *nth == status_reads),
+        ) {
+            if let Fault::CorruptStatus { mask, .. } = self.faults.remove(position) {
+                status ^= mask;
+            }
+        }
+        status
+    }
+}
+
+/// Error returned by [`SimBus`] transactions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SimError {
+    kind: ErrorKind,
+}
+
+impl SimError {
+    fn nack() -> SimError {
+        SimError {
+            kind: ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        }
+    }
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "simulated I2C error: {}", self.kind)
+    }
+}
+
+impl std::error::Error for SimError {}
+
+impl i2c::Error for SimError {
+    fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+#[derive(Debug, Default)]
+struct SimState {
+    boards: Vec<SimulatedBoard>,
+    transactions: usize,
+}
+
+/// A virtual I2C bus with zero or more simulated relay boards attached.
+#[derive(Debug, Clone, Default)]
+pub struct SimBus {
+    state: Arc<Mutex<SimState>>,
+}
+
+impl SimBus {
+    /// Creates an empty bus; every address NACKs until a board is attached.
+    pub fn new() -> SimBus {
+        SimBus::default()
+    }
+
+    /// Attaches a board and returns the bus, for building a bus in one expression.
+    pub fn with_board(self, board: SimulatedBoard) -> SimBus {
+        self.attach(board);
+        self
+    }
+
+    /// Attaches a board to the bus.
+    pub fn attach(&self, board: SimulatedBoard) {
+        self.lock().boards.push(board);
+    }
+
+    /// Returns a snapshot of the board currently answering at `address`.
+    pub fn board(&self, address: u8) -> Option<SimulatedBoard> {
+        self.lock()
+            .boards
+            .iter()
+            .find(|board| board.address == address)
+            .cloned()
+    }
+
+    /// Returns the state of a relay on the board at `address`.
+    pub fn relay_state(&self, address: u8, relay_num: u8) -> Option<bool> {
+        self.board(address)
+            .and_then(|board| board.relay_state(relay_num))
+    }
+
+    /// Sets the state of a relay on the board at `address`, as if switched by hand.
+    pub fn set_relay_state(&self, address: u8, relay_num: u8, on: bool) {
+        if let Some(board) = self.lock().boards.iter_mut().find(|b| b.address == address) {
+            board.set_relay_state(relay_num, on);
+        }
+    }
+
+    /// Injects a fault into the board at `address`; does nothing if no board answers there.
+    pub fn inject(&self, address: u8, fault: Fault) {
+        if let Some(board) = self.lock().boards.iter_mut().find(|b| b.address == address) {
+            board.inject(fault);
+        }
+    }
+
+    /// Removes all faults from the board at `address`.
+    pub fn clear_faults(&self, address: u8) {
+        if let Some(board) = self.lock().boards.iter_mut().find(|b| b.address == address) {
+            board.clear_faults();
+        }
+    }
+
+    /// Number of I2C transactions issued on this bus so far, including NACKed ones.
+    pub fn transaction_count(&self) -> usize {
+        self.lock().transactions
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SimState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn execute(
+        &self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), SimError> {
+        let mut state = self.lock();
+        state.transactions += 1;
+
+        let board = state
+            .boards
+            .iter_mut()
+            .find(|board| board.address == address)
+            .ok_or_else(SimError::nack)?;
+        board.begin_transaction()?;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => board.handle_write(bytes),
+                Operation::Read(buffer) => buffer.fill(board.read_register()),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl i2c::ErrorType for SimBus {
+    type Error = SimError;
+}
+
+impl i2c::I2c for SimBus {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl embedded_hal_async::i2c::I2c for SimBus {
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.execute(address, operations)
+    }
+}
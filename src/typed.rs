@@ -0,0 +1,361 @@
+//! Boards whose type fixes the relay count, so relay numbers are checked at compile time.
+//!
+//! [`SingleRelay`], [`QuadRelay`], [`DualSsr`] and [`QuadSsr`] wrap a
+//! [`QwiicRelay`] configured for that board. Multi relay boards take a
+//! [`Relay`], whose number is checked against the board when the program is
+//! built; single relay boards take no relay number at all.
+//!
+//! # Examples
+//! ```
+//! use qwiic_relay_rs::sim::{SimBus, SimulatedBoard};
+//! use qwiic_relay_rs::typed::{QuadRelay, Relay, SingleRelay};
+//! use qwiic_relay_rs::RelayStatus;
+//!
+//! let bus = SimBus::new()
+//!     .with_board(SimulatedBoard::quad())
+//!     .with_board(SimulatedBoard::single());
+//!
+//! let mut quad = QuadRelay::from_board(bus.clone(), false);
+//! quad.set_on(Relay::new::<3>()).unwrap();
+//! assert_eq!(quad.state(Relay::new::<3>()).unwrap(), RelayStatus::On);
+//!
+//! let mut single = SingleRelay::from_board(bus.clone(), false);
+//! single.on().unwrap();
+//! assert_eq!(bus.relay_state(0x18, 1), Some(true));
+//! ```
+//!
+//! A relay the board does not have is a build error:
+//! ```compile_fail
+//! use qwiic_relay_rs::typed::{QuadRelay, Relay};
+//!
+//! let mut quad = QuadRelay::open("/dev/i2c-1", false).unwrap();
+//! quad.set_on(Relay::new::<5>()).unwrap();
+//! ```
+//! and so is a relay number on a single relay board:
+//! ```compile_fail
+//! use qwiic_relay_rs::typed::{Relay, SingleRelay};
+//!
+//! let mut single = SingleRelay::open("/dev/i2c-1", false).unwrap();
+//! single.set_on(Relay::new::<1>()).unwrap();
+//! ```
+//! The relay count cannot be changed under the type either:
+//! ```compile_fail
+//! use qwiic_relay_rs::typed::QuadRelay;
+//! use qwiic_relay_rs::QwiicRelayConfig;
+//!
+//! let mut quad = QuadRelay::open("/dev/i2c-1", false).unwrap();
+//! quad.driver().update_config(QwiicRelayConfig::new(2)).unwrap();
+//! ```
+//! and PWM is only offered on solid state boards:
+//! ```compile_fail
+//! use qwiic_relay_rs::typed::{QuadRelay, Relay};
+//!
+//! let mut quad = QuadRelay::open("/dev/i2c-1", false).unwrap();
+//! quad.set_pwm(Relay::new::<1>(), 60).unwrap();
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::registers::Channel;
+use crate::{
+    BankOutcome, BoardInfo, BoardKind, LinuxBus, QwiicRelay, RelayChannel, RelayResult,
+    RelayStatus, VerificationOutcome,
+};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A board type known at compile time.
+pub trait Board: sealed::Sealed {
+    /// The board this type stands for.
+    const KIND: BoardKind;
+    /// Number of relays on the board.
+    const RELAYS: u8;
+}
+
+/// A board with more than one relay, whose relays are picked with a [`Relay`].
+pub trait MultiRelayBoard: Board {}
+
+/// A solid state board, whose relays can run slow PWM.
+pub trait PwmBoard: MultiRelayBoard {}
+
+/// The single relay board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SingleRelayBoard {}
+
+/// The quad relay board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuadRelayBoard {}
+
+/// The dual solid state relay board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DualSsrBoard {}
+
+/// The quad solid state relay board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuadSsrBoard {}
+
+impl sealed::Sealed for SingleRelayBoard {}
+impl sealed::Sealed for QuadRelayBoard {}
+impl sealed::Sealed for DualSsrBoard {}
+impl sealed::Sealed for QuadSsrBoard {}
+
+impl Board for SingleRelayBoard {
+    const KIND: BoardKind = BoardKind::SingleRelay;
+    const RELAYS: u8 = 1;
+}
+
+impl Board for QuadRelayBoard {
+    const KIND: BoardKind = BoardKind::QuadRelay;
+    const RELAYS: u8 = 4;
+}
+
+impl Board for DualSsrBoard {
+    const KIND: BoardKind = BoardKind::DualSolidState;
+    const RELAYS: u8 = 2;
+}
+
+impl Board for QuadSsrBoard {
+    const KIND: BoardKind = BoardKind::QuadSolidState;
+    const RELAYS: u8 = 4;
+}
+
+impl MultiRelayBoard for QuadRelayBoard {}
+impl MultiRelayBoard for DualSsrBoard {}
+impl MultiRelayBoard for QuadSsrBoard {}
+
+impl PwmBoard for DualSsrBoard {}
+impl PwmBoard for QuadSsrBoard {}
+
+/// A relay number that is known to exist on board `B`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Relay<B: MultiRelayBoard> {
+    num: u8,
+    board: PhantomData<B>,
+}
+
+impl<B: MultiRelayBoard> Relay<B> {
+    /// Relay `R`, checked against the board when the program is built.
+    pub const fn new<const R: u8>() -> Relay<B> {
+        const { assert!(R >= 1 && R <= B::RELAYS, "the board has no relay with this number") };
+        Relay {
+            num: R,
+            board: PhantomData,
+        }
+    }
+
+    /// Relay `relay_num`, or `None` if the board has no such relay.
+    ///
+    /// For relay numbers that are only known at run time.
+    pub const fn checked(relay_num: u8) -> Option<Relay<B>> {
+        if relay_num >= 1 && relay_num <= B::RELAYS {
+            Some(Relay {
+                num: relay_num,
+                board: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Every relay on the board, in order.
+    pub fn all() -> impl Iterator<Item = Relay<B>> {
+        (1..=B::RELAYS).map(|num| Relay {
+            num,
+            board: PhantomData,
+        })
+    }
+
+    /// The relay number, from 1.
+    pub const fn get(self) -> u8 {
+        self.num
+    }
+}
+
+/// A [`QwiicRelay`] whose board type is part of its type.
+pub struct TypedRelay<B: Board, I2C = LinuxBus> {
+    relay: QwiicRelay<I2C>,
+    board: PhantomData<B>,
+}
+
+/// A single relay board.
+pub type SingleRelay<I2C = LinuxBus> = TypedRelay<SingleRelayBoard, I2C>;
+/// A quad relay board.
+pub type QuadRelay<I2C = LinuxBus> = TypedRelay<QuadRelayBoard, I2C>;
+/// A dual solid state relay board.
+pub type DualSsr<I2C = LinuxBus> = TypedRelay<DualSsrBoard, I2C>;
+/// A quad solid state relay board.
+pub type QuadSsr<I2C = LinuxBus> = TypedRelay<QuadSsrBoard, I2C>;
+
+impl<B: Board, I2C> fmt::Debug for TypedRelay<B, I2C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedRelay")
+            .field("board", &B::KIND)
+            .field("relay", &self.relay)
+            .finish()
+    }
+}
+
+impl<B: Board> TypedRelay<B, LinuxBus> {
+    /// Opens the board on a Linux I2C bus at its default or jumper address.
+    ///
+    /// Like [`QwiicRelay::for_board`], this waits for the board to answer.
+    ///
+    /// # Arguments
+    /// * `bus` - I2C bus path (e.g., "/dev/i2c-1")
+    /// * `jumper_closed` - Whether the board's address jumper is closed
+    pub fn open(bus: &str, jumper_closed: bool) -> RelayResult<TypedRelay<B>> {
+        Ok(TypedRelay::wrap(QwiicRelay::for_board(B::KIND, bus, jumper_closed)?))
+    }
+}
+
+impl<B: Board, I2C: BlockingI2c> TypedRelay<B, I2C> {
+    fn wrap(relay: QwiicRelay<I2C>) -> TypedRelay<B, I2C> {
+        TypedRelay {
+            relay,
+            board: PhantomData,
+        }
+    }
+
+    /// Creates the board on an existing embedded-hal I2C bus at its default or jumper address.
+    ///
+    /// Like [`QwiicRelay::from_board`], this does not wait for the board to boot.
+    pub fn from_board(i2c: I2C, jumper_closed: bool) -> TypedRelay<B, I2C> {
+        TypedRelay::wrap(QwiicRelay::from_board(B::KIND, i2c, jumper_closed))
+    }
+
+    /// Creates the board on an existing embedded-hal I2C bus at a reprogrammed address.
+    pub fn from_i2c(i2c: I2C, address: u8) -> TypedRelay<B, I2C> {
+        let mut relay = QwiicRelay::from_i2c(B::KIND.config(), i2c, address);
+        relay.board = Some(B::KIND);
+        TypedRelay::wrap(relay)
+    }
+
+    /// The untyped driver, for its configuration and diagnostics.
+    ///
+    /// The borrow is shared so the relay count cannot be changed under the
+    /// board type; [`TypedRelay::into_driver`] gives up the type instead.
+    pub fn driver(&self) -> &QwiicRelay<I2C> {
+        &self.relay
+    }
+
+    /// Consumes the wrapper and returns the untyped driver.
+    pub fn into_driver(self) -> QwiicRelay<I2C> {
+        self.relay
+    }
+
+    /// See [`QwiicRelay::init`].
+    pub fn init(&mut self) -> RelayResult<BoardInfo, I2C::Error> {
+        self.relay.init()
+    }
+
+    /// See [`QwiicRelay::get_relays`].
+    pub fn get_relays(&mut self) -> RelayResult<u8, I2C::Error> {
+        self.relay.get_relays()
+    }
+
+    /// See [`QwiicRelay::set_relays`].
+    pub fn set_relays(&mut self, mask: u8) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relays(mask)
+    }
+
+    /// See [`QwiicRelay::set_all_relays_on`].
+    pub fn set_all_relays_on(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_all_relays_on()
+    }
+
+    /// See [`QwiicRelay::set_all_relays_off`].
+    pub fn set_all_relays_off(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_all_relays_off()
+    }
+
+    /// See [`QwiicRelay::toggle_all_relays`].
+    pub fn toggle_all_relays(&mut self) -> RelayResult<BankOutcome, I2C::Error> {
+        self.relay.toggle_all_relays()
+    }
+}
+
+impl<I2C: BlockingI2c> TypedRelay<SingleRelayBoard, I2C> {
+    /// Turns the relay on.
+    pub fn on(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relay(None, RelayStatus::On)
+    }
+
+    /// Turns the relay off.
+    pub fn off(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relay(None, RelayStatus::Off)
+    }
+
+    /// Flips the relay.
+    pub fn toggle(&mut self) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.toggle_relay(None)
+    }
+
+    /// Reads the relay's state.
+    pub fn state(&mut self) -> RelayResult<RelayStatus, I2C::Error> {
+        self.relay.get_relay_state(None)
+    }
+
+    /// Turns the relay on for `duration`, then restores it.
+    pub fn pulse(&mut self, duration: Duration) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.pulse(None, duration)
+    }
+
+    /// Borrows the relay as a [`RelayChannel`], for code that expects an output pin.
+    pub fn channel(&mut self) -> RelayChannel<'_, I2C> {
+        RelayChannel::new(&mut self.relay, Channel::Single)
+    }
+}
+
+impl<B: MultiRelayBoard, I2C: BlockingI2c> TypedRelay<B, I2C> {
+    /// Turns a relay on.
+    pub fn set_on(&mut self, relay: Relay<B>) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relay(Some(relay.get()), RelayStatus::On)
+    }
+
+    /// Turns a relay off.
+    pub fn set_off(&mut self, relay: Relay<B>) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.set_relay(Some(relay.get()), RelayStatus::Off)
+    }
+
+    /// Flips a relay.
+    pub fn toggle(&mut self, relay: Relay<B>) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.toggle_relay(Some(relay.get()))
+    }
+
+    /// Reads a relay's state.
+    pub fn state(&mut self, relay: Relay<B>) -> RelayResult<RelayStatus, I2C::Error> {
+        self.relay.get_relay_state(Some(relay.get()))
+    }
+
+    /// Turns a relay on for `duration`, then restores it.
+    pub fn pulse(
+        &mut self,
+        relay: Relay<B>,
+        duration: Duration,
+    ) -> RelayResult<VerificationOutcome, I2C::Error> {
+        self.relay.pulse(Some(relay.get()), duration)
+    }
+
+    /// Borrows a relay as a [`RelayChannel`], for code that expects an output pin.
+    pub fn channel(&mut self, relay: Relay<B>) -> RelayChannel<'_, I2C> {
+        RelayChannel::new(&mut self.relay, Channel::Relay(relay.get()))
+    }
+}
+
+impl<B: PwmBoard, I2C: BlockingI2c> TypedRelay<B, I2C> {
+    /// See [`QwiicRelay::set_pwm`].
+    pub fn set_pwm(&mut self, relay: Relay<B>, duty: u8) -> RelayResult<(), I2C::Error> {
+        self.relay.set_pwm(relay.get(), duty)
+    }
+
+    /// See [`QwiicRelay::get_pwm`].
+    pub fn get_pwm(&mut self, relay: Relay<B>) -> RelayResult<u8, I2C::Error> {
+        self.relay.get_pwm(relay.get())
+    }
+}
@@ -0,0 +1,295 @@
+//! Relays whose on/off state is tracked in their type.
+//!
+//! A [`Relay<Off>`](Relay) can only be turned on and a [`Relay<On>`](Relay)
+//! only off; each switch consumes the relay and returns it in its new state,
+//! once the board has confirmed it. An [`Interlock`] holds two relays that must
+//! never be on together, such as a motor's forward and reverse contactors, and
+//! has no state in which both are on.
+//!
+//! A switch that fails consumes the relay, since its real state is then
+//! unknown. Turning a relay on that fails is followed by a best-effort switch
+//! back off. Getting the relay again with [`RelayChannel::into_off`] or
+//! [`QwiicRelay::interlock`] switches it off and confirms that first.
+//!
+//! A Lenient-mode verification warning counts as a failure here, because
+//! carrying on in a state the board did not confirm would defeat the types.
+//! With verification disabled the relay is still read back once, bypassing the
+//! state cache, before its type changes.
+//!
+//! # Examples
+//! ```
+//! use qwiic_relay_rs::sim::{SimBus, SimulatedBoard};
+//! use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
+//!
+//! let bus = SimBus::new().with_board(SimulatedBoard::quad());
+//! let mut relay = QwiicRelay::from_i2c(QwiicRelayConfig::new(4), bus.clone(), 0x6D);
+//!
+//! // Relay 1 drives forward, relay 2 reverse
+//! let motor = relay.interlock(1, 2).unwrap();
+//! let forward = motor.first_on().unwrap();
+//! assert_eq!(bus.relay_state(0x6D, 1), Some(true));
+//!
+//! // Break before make: forward is confirmed off before reverse comes on
+//! let reverse = forward.swap().unwrap();
+//! assert_eq!(bus.relay_state(0x6D, 1), Some(false));
+//! assert_eq!(bus.relay_state(0x6D, 2), Some(true));
+//! reverse.second_off().unwrap();
+//! ```
+//!
+//! Both relays on is not a state the interlock can reach:
+//! ```compile_fail
+//! use qwiic_relay_rs::{QwiicRelay, QwiicRelayConfig};
+//!
+//! let mut relay = QwiicRelay::new(QwiicRelayConfig::new(4), "/dev/i2c-1", 0x6D).unwrap();
+//! let motor = relay.interlock(1, 2).unwrap();
+//! motor.first_on().unwrap().second_on().unwrap();
+//! ```
+
+use std::marker::PhantomData;
+
+use embedded_hal::i2c::I2c as BlockingI2c;
+
+use crate::registers::Channel;
+use crate::{
+    LinuxBus, QwiicRelay, RelayChannel, RelayError, RelayResult, RelayStatus, VerificationOutcome,
+};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A relay state that can appear in a type.
+pub trait State: sealed::Sealed {
+    /// The relay state this type stands for.
+    const STATUS: RelayStatus;
+}
+
+/// The relay is confirmed on.
+#[derive(Debug)]
+pub enum On {}
+
+/// The relay is confirmed off.
+#[derive(Debug)]
+pub enum Off {}
+
+impl sealed::Sealed for On {}
+impl sealed::Sealed for Off {}
+
+impl State for On {
+    const STATUS: RelayStatus = RelayStatus::On;
+}
+
+impl State for Off {
+    const STATUS: RelayStatus = RelayStatus::Off;
+}
+
+/// Switches a channel and requires the board to confirm it.
+///
+/// A relay that was being turned on is switched back off if that fails.
+fn switch<I2C: BlockingI2c>(
+    relay: &mut QwiicRelay<I2C>,
+    channel: Channel,
+    target: RelayStatus,
+) -> RelayResult<(), I2C::Error> {
+    let result = relay
+        .set_relay(channel.relay_num(), target)
+        .and_then(|outcome| confirmed(relay, channel, target, outcome));
+    if result.is_err() && target == RelayStatus::On {
+        let _ = relay.set_relay(channel.relay_num(), RelayStatus::Off);
+    }
+    result
+}
+
+/// Checks that the board reported `target` after a switch.
+///
+/// A Lenient-mode warning becomes the error Strict mode would have returned, and
+/// with verification disabled the relay is read back here, past the cache.
+fn confirmed<I2C: BlockingI2c>(
+    relay: &mut QwiicRelay<I2C>,
+    channel: Channel,
+    target: RelayStatus,
+    outcome: VerificationOutcome,
+) -> RelayResult<(), I2C::Error> {
+    match outcome {
+        VerificationOutcome::Verified => Ok(()),
+        VerificationOutcome::Skipped => {
+            if relay.read_relay_state(channel)? == target {
+                Ok(())
+            } else {
+                Err(RelayError::VerificationFailed {
+                    relay_num: channel.relay_num(),
+                    expected: target,
+                    attempts: 1,
+                })
+            }
+        }
+        VerificationOutcome::Warning(warning) if warning.timed_out => {
+            Err(RelayError::VerificationTimeout {
+                relay_num: warning.relay_num,
+                expected: warning.expected,
+                timeout_ms: relay.config.verification.timeout_ms,
+            })
+        }
+        VerificationOutcome::Warning(warning) => Err(RelayError::VerificationFailed {
+            relay_num: warning.relay_num,
+            expected: warning.expected,
+            attempts: warning.attempts,
+        }),
+    }
+}
+
+/// One relay whose state `S` is [`On`] or [`Off`].
+///
+/// Created with [`RelayChannel::into_off`] or [`RelayChannel::into_on`].
+#[derive(Debug)]
+pub struct Relay<'a, S: State, I2C: BlockingI2c = LinuxBus> {
+    relay: &'a mut QwiicRelay<I2C>,
+    channel: Channel,
+    state: PhantomData<S>,
+}
+
+impl<'a, S: State, I2C: BlockingI2c> Relay<'a, S, I2C> {
+    /// Switches `channel` to `S` and wraps it once the board confirms it.
+    pub(crate) fn switched(
+        relay: &'a mut QwiicRelay<I2C>,
+        channel: Channel,
+    ) -> RelayResult<Relay<'a, S, I2C>, I2C::Error> {
+        switch(relay, channel, S::STATUS)?;
+        Ok(Relay {
+            relay,
+            channel,
+            state: PhantomData,
+        })
+    }
+
+    /// The relay's number on the board.
+    pub fn relay_num(&self) -> u8 {
+        self.channel.relay_num().unwrap_or(1)
+    }
+
+    /// Gives up the typed state and returns the untyped channel.
+    pub fn into_channel(self) -> RelayChannel<'a, I2C> {
+        RelayChannel::new(self.relay, self.channel)
+    }
+}
+
+impl<'a, I2C: BlockingI2c> Relay<'a, Off, I2C> {
+    /// Turns the relay on.
+    pub fn turn_on(self) -> RelayResult<Relay<'a, On, I2C>, I2C::Error> {
+        Relay::switched(self.relay, self.channel)
+    }
+}
+
+impl<'a, I2C: BlockingI2c> Relay<'a, On, I2C> {
+    /// Turns the relay off.
+    pub fn turn_off(self) -> RelayResult<Relay<'a, Off, I2C>, I2C::Error> {
+        Relay::switched(self.relay, self.channel)
+    }
+}
+
+/// Two relays on one board that must never be on together.
+///
+/// `A` is the state of the first relay and `B` of the second. Only
+/// `Interlock<Off, Off>`, `Interlock<On, Off>` and `Interlock<Off, On>` can be
+/// created, so no sequence of calls turns both on.
+#[derive(Debug)]
+pub struct Interlock<'a, A: State, B: State, I2C: BlockingI2c = LinuxBus> {
+    relay: &'a mut QwiicRelay<I2C>,
+    first: Channel,
+    second: Channel,
+    state: PhantomData<(A, B)>,
+}
+
+impl<I2C: BlockingI2c> QwiicRelay<I2C> {
+    /// Pairs two relays in an [`Interlock`], switching both off first.
+    ///
+    /// The interlock borrows the driver, so nothing else can switch either
+    /// relay while it exists.
+    ///
+    /// # Arguments
+    /// * `first` - The first relay's number (e.g. a motor's forward contactor)
+    /// * `second` - The second relay's number (e.g. its reverse contactor)
+    ///
+    /// # Errors
+    /// `InvalidConfiguration` if both are the same relay, `InvalidRelayNumber` if
+    /// the board lacks either, or the error from switching them off.
+    pub fn interlock(
+        &mut self,
+        first: u8,
+        second: u8,
+    ) -> RelayResult<Interlock<'_, Off, Off, I2C>, I2C::Error> {
+        if first == second {
+            return Err(RelayError::InvalidConfiguration(
+                "an interlock needs two different relays",
+            ));
+        }
+        let first = self.numbered_channel(first)?;
+        let second = self.numbered_channel(second)?;
+        switch(self, first, RelayStatus::Off)?;
+        switch(self, second, RelayStatus::Off)?;
+        Ok(Interlock {
+            relay: self,
+            first,
+            second,
+            state: PhantomData,
+        })
+    }
+}
+
+impl<'a, A: State, B: State, I2C: BlockingI2c> Interlock<'a, A, B, I2C> {
+    fn into_state<C: State, D: State>(self) -> Interlock<'a, C, D, I2C> {
+        Interlock {
+            relay: self.relay,
+            first: self.first,
+            second: self.second,
+            state: PhantomData,
+        }
+    }
+
+    /// Switches both relays off, from any state.
+    pub fn all_off(self) -> RelayResult<Interlock<'a, Off, Off, I2C>, I2C::Error> {
+        switch(self.relay, self.first, RelayStatus::Off)?;
+        switch(self.relay, self.second, RelayStatus::Off)?;
+        Ok(self.into_state())
+    }
+}
+
+impl<'a, I2C: BlockingI2c> Interlock<'a, Off, Off, I2C> {
+    /// Turns the first relay on.
+    pub fn first_on(self) -> RelayResult<Interlock<'a, On, Off, I2C>, I2C::Error> {
+        switch(self.relay, self.first, RelayStatus::On)?;
+        Ok(self.into_state())
+    }
+
+    /// Turns the second relay on.
+    pub fn second_on(self) -> RelayResult<Interlock<'a, Off, On, I2C>, I2C::Error> {
+        switch(self.relay, self.second, RelayStatus::On)?;
+        Ok(self.into_state())
+    }
+}
+
+impl<'a, I2C: BlockingI2c> Interlock<'a, On, Off, I2C> {
+    /// Turns the first relay off.
+    pub fn first_off(self) -> RelayResult<Interlock<'a, Off, Off, I2C>, I2C::Error> {
+        switch(self.relay, self.first, RelayStatus::Off)?;
+        Ok(self.into_state())
+    }
+
+    /// Turns the first relay off and, once that is confirmed, the second on.
+    pub fn swap(self) -> RelayResult<Interlock<'a, Off, On, I2C>, I2C::Error> {
+        self.first_off()?.second_on()
+    }
+}
+
+impl<'a, I2C: BlockingI2c> Interlock<'a, Off, On, I2C> {
+    /// Turns the second relay off.
+    pub fn second_off(self) -> RelayResult<Interlock<'a, Off, Off, I2C>, I2C::Error> {
+        switch(self.relay, self.second, RelayStatus::Off)?;
+        Ok(self.into_state())
+    }
+
+    /// Turns the second relay off and, once that is confirmed, the first on.
+    pub fn swap(self) -> RelayResult<Interlock<'a, On, Off, I2C>, I2C::Error> {
+        self.second_off()?.first_on()
+    }
+}
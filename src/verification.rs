@@ -1,19 +1,19 @@
-use std::time::Duration;
+use core::fmt;
+use core::time::Duration;
 
-#[derive(Clone, Copy, Debug)]
+use crate::RelayStatus;
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum VerificationMode {
+    #[default]
     Strict,
     Lenient,
     Disabled,
 }
 
-impl Default for VerificationMode {
-    fn default() -> Self {
-        VerificationMode::Strict
-    }
-}
-
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct VerificationConfig {
     pub mode: VerificationMode,
     pub max_retries: u8,
@@ -95,4 +95,103 @@ impl VerificationConfig {
     pub fn timeout(&self) -> Duration {
         Duration::from_millis(self.timeout_ms)
     }
-}
\ No newline at end of file
+}
+
+/// Result of checking a relay's state after an operation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum VerificationOutcome {
+    /// The relay was read back in the expected state.
+    Verified,
+    /// Verification is disabled, so the state was not read back.
+    Skipped,
+    /// Lenient mode only: the relay did not reach the expected state.
+    Warning(VerificationWarning),
+}
+
+impl VerificationOutcome {
+    /// Returns `true` unless this is a warning.
+    pub fn is_ok(&self) -> bool {
+        !matches!(self, VerificationOutcome::Warning(_))
+    }
+
+    /// Combines the outcomes of several checks, keeping the first warning.
+    pub(crate) fn and(self, next: VerificationOutcome) -> VerificationOutcome {
+        match (self, next) {
+            (VerificationOutcome::Warning(_), _) => self,
+            _ => next,
+        }
+    }
+}
+
+/// Per-relay verification outcomes of an operation on the whole bank.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BankOutcome {
+    /// Indexed by relay number - 1.
+    outcomes: [Option<VerificationOutcome>; 8],
+}
+
+impl BankOutcome {
+    /// The outcome for a relay (1-based; a single relay board's relay is 1).
+    pub fn get(&self, relay_num: u8) -> Option<VerificationOutcome> {
+        let index = usize::from(relay_num).checked_sub(1)?;
+        self.outcomes.get(index).copied().flatten()
+    }
+
+    /// Relay numbers with their outcomes, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, VerificationOutcome)> + '_ {
+        (1..)
+            .zip(self.outcomes.iter())
+            .filter_map(|(relay_num, outcome)| outcome.map(|outcome| (relay_num, outcome)))
+    }
+
+    /// All outcomes combined, keeping the first warning.
+    pub fn overall(&self) -> VerificationOutcome {
+        self.iter()
+            .fold(VerificationOutcome::Skipped, |outcome, (_, next)| outcome.and(next))
+    }
+
+    /// Returns `true` unless any relay has a warning.
+    pub fn is_ok(&self) -> bool {
+        self.iter().all(|(_, outcome)| outcome.is_ok())
+    }
+
+    /// Records the outcome for the relay at mask bit `bit`.
+    pub(crate) fn record(&mut self, bit: u8, outcome: VerificationOutcome) {
+        if let Some(slot) = self.outcomes.get_mut(usize::from(bit)) {
+            *slot = Some(outcome);
+        }
+    }
+}
+
+/// A verification mismatch tolerated in Lenient mode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VerificationWarning {
+    pub relay_num: Option<u8>,
+    pub expected: RelayStatus,
+    /// The last state read back, if any read succeeded.
+    pub actual: Option<RelayStatus>,
+    pub attempts: u8,
+    /// Whether verification gave up because `timeout_ms` elapsed.
+    pub timed_out: bool,
+}
+
+impl fmt::Display for VerificationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.relay_num {
+            Some(n) => write!(f, "relay {}", n)?,
+            None => write!(f, "relay")?,
+        }
+        write!(f, " expected {:?}", self.expected)?;
+        if let Some(actual) = self.actual {
+            write!(f, ", read {:?}", actual)?;
+        }
+        if self.timed_out {
+            write!(f, " (timed out)")
+        } else {
+            write!(f, " after {} attempts", self.attempts)
+        }
+    }
+}